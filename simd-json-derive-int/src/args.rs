@@ -1,14 +1,35 @@
-use proc_macro2::{Ident, Literal};
-use simd_json::OwnedValue;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
 use simd_json::prelude::*;
-use syn::{LitStr, parse::{Parse, ParseStream}, Path, Variant};
-use syn::{Attribute, Field, Token};
+use simd_json::OwnedValue;
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    visit::Visit,
+    Fields, Lifetime, LitStr, Member, Path, Type, TypePath, Variant, WherePredicate,
+};
+use syn::{Attribute, Data, DataEnum, Field, Token};
 
 #[derive(Debug, Default)]
 pub(crate) struct FieldAttrs {
-    rename: Option<String>,
+    rename: Directional<String>,
     skip_serializing_if: Option<Path>,
-    default: bool,
+    default: Option<DefaultValue>,
+    flatten: bool,
+    extra: bool,
+    skip: bool,
+    skip_serializing: bool,
+    skip_deserializing: bool,
+    with: Option<Path>,
+    serialize_with: Option<Path>,
+    deserialize_with: Option<Path>,
+    aliases: Vec<String>,
+    optional: bool,
+    borrow: Option<Vec<Lifetime>>,
+    getter: Option<Path>,
 }
 
 impl Parse for FieldAttrs {
@@ -19,10 +40,7 @@ impl Parse for FieldAttrs {
             let attr: Ident = input.parse()?;
             match attr.to_string().as_str() {
                 "rename" => {
-                    let _equal_token: Token![=] = input.parse()?;
-                    let name: LitStr = input.parse()?;
-
-                    attrs.rename = Some(name.value());
+                    attrs.rename = Directional::parse(input, |name| Ok(name.value()))?;
                 }
                 "skip_serializing_if" => {
                     let _equal_token: Token![=] = input.parse()?;
@@ -33,9 +51,70 @@ impl Parse for FieldAttrs {
                     attrs.skip_serializing_if = Some(path);
                 }
                 "default" => {
-                    attrs.default = true;
+                    attrs.default = Some(DefaultValue::parse(input)?);
+                }
+                "flatten" => {
+                    attrs.flatten = true;
+                }
+                "extra" => {
+                    attrs.extra = true;
+                }
+                "skip" => {
+                    attrs.skip = true;
+                }
+                "skip_serializing" => {
+                    attrs.skip_serializing = true;
+                }
+                "skip_deserializing" => {
+                    attrs.skip_deserializing = true;
+                }
+                "with" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let module: LitStr = input.parse()?;
+
+                    attrs.with = Some(module.parse()?);
+                }
+                "serialize_with" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let function: LitStr = input.parse()?;
+
+                    attrs.serialize_with = Some(function.parse()?);
+                }
+                "deserialize_with" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let function: LitStr = input.parse()?;
+
+                    attrs.deserialize_with = Some(function.parse()?);
+                }
+                "alias" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let name: LitStr = input.parse()?;
+
+                    attrs.aliases.push(name.value());
+                }
+                "optional" => {
+                    attrs.optional = true;
+                }
+                "getter" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let function: LitStr = input.parse()?;
+
+                    attrs.getter = Some(function.parse()?);
+                }
+                "borrow" => {
+                    // `borrow` borrows all lifetimes of the field, `borrow = "'a + 'b"` only the given ones
+                    let lifetimes = if input.peek(Token![=]) {
+                        let _equal_token: Token![=] = input.parse()?;
+                        let lifetimes: LitStr = input.parse()?;
+                        lifetimes
+                            .parse_with(Punctuated::<Lifetime, Token![+]>::parse_terminated)?
+                            .into_iter()
+                            .collect()
+                    } else {
+                        Vec::new()
+                    };
+                    attrs.borrow = Some(lifetimes);
                 }
-                "borrow" => (),
                 other => {
                     return Err(syn::Error::new(
                         attr.span(),
@@ -51,9 +130,91 @@ impl Parse for FieldAttrs {
     }
 }
 
+/// How a field that may be missing from the input is read
+pub(crate) enum Optional<'field> {
+    /// An `Option<T>`, a missing key and `null` both give `None`
+    Option,
+    /// An `Option<Option<T>>`, a missing key gives `None` and `null` gives
+    /// `Some(None)`, the inner `Option<T>` is read
+    Double(&'field Type),
+}
+
+/// The `T` of `Option<T>`, `std::option::Option<T>` or `core::option::Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(TypePath { qself: None, path }) => path,
+        Type::Group(group) => return option_inner(&group.elem),
+        Type::Paren(paren) => return option_inner(&paren.elem),
+        _ => return None,
+    };
+    let prefix: Vec<String> = path
+        .segments
+        .iter()
+        .rev()
+        .skip(1)
+        .map(|s| s.ident.to_string())
+        .collect();
+    let is_option_path = match prefix.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] => path.leading_colon.is_none(),
+        ["option"] => path.leading_colon.is_none(),
+        ["option", "std" | "core"] => true,
+        _ => false,
+    };
+    let last = path.segments.last()?;
+    if !is_option_path || last.ident != "Option" {
+        return None;
+    }
+    match &last.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The lifetimes a type refers to
+fn lifetimes_of(ty: &Type) -> Vec<Lifetime> {
+    struct Lifetimes(Vec<Lifetime>);
+    impl<'ast> Visit<'ast> for Lifetimes {
+        fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+            if !self.0.contains(lifetime) {
+                self.0.push(lifetime.clone());
+            }
+        }
+    }
+    let mut lifetimes = Lifetimes(Vec::new());
+    lifetimes.visit_type(ty);
+    lifetimes.0
+}
+
+/// Where the value of a missing field comes from
+#[derive(Debug, Clone)]
+pub(crate) enum DefaultValue {
+    /// `default`, uses `Default::default()`
+    Trait,
+    /// `default = "path"`, calls `path()`
+    Path(Path),
+}
+
+impl DefaultValue {
+    /// Parses the optional `= "path"` following `default`
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![=]) {
+            let _equal_token: Token![=] = input.parse()?;
+            let function: LitStr = input.parse()?;
+            Ok(DefaultValue::Path(function.parse()?))
+        } else {
+            Ok(DefaultValue::Trait)
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct VariantAttrs {
-    rename: Option<String>,
+    rename: Directional<String>,
+    aliases: Vec<String>,
+    other: bool,
 }
 
 impl Parse for VariantAttrs {
@@ -64,10 +225,16 @@ impl Parse for VariantAttrs {
             let attr: Ident = input.parse()?;
             match attr.to_string().as_str() {
                 "rename" => {
+                    attrs.rename = Directional::parse(input, |name| Ok(name.value()))?;
+                }
+                "alias" => {
                     let _equal_token: Token![=] = input.parse()?;
                     let name: LitStr = input.parse()?;
 
-                    attrs.rename = Some(name.value());
+                    attrs.aliases.push(name.value());
+                }
+                "other" => {
+                    attrs.other = true;
                 }
                 other => {
                     return Err(syn::Error::new(
@@ -84,89 +251,304 @@ impl Parse for VariantAttrs {
     }
 }
 
-#[derive(Debug)]
+/// A value that can differ between serializing and deserializing, given as
+/// `attr = ".."` for both or as `attr(serialize = "..", deserialize = "..")`
+#[derive(Debug, Clone)]
+pub(crate) struct Directional<T> {
+    serialize: Option<T>,
+    deserialize: Option<T>,
+}
+
+impl<T> Default for Directional<T> {
+    fn default() -> Self {
+        Directional {
+            serialize: None,
+            deserialize: None,
+        }
+    }
+}
+
+impl<T: Clone> Directional<T> {
+    fn parse(input: ParseStream, value: impl Fn(&LitStr) -> syn::Result<T>) -> syn::Result<Self> {
+        if input.peek(Token![=]) {
+            let _equal_token: Token![=] = input.parse()?;
+            let v = value(&input.parse()?)?;
+            return Ok(Directional {
+                serialize: Some(v.clone()),
+                deserialize: Some(v),
+            });
+        }
+        let content;
+        syn::parenthesized!(content in input);
+        let mut renamed = Directional::default();
+        while !content.is_empty() {
+            let attr: Ident = content.parse()?;
+            let _equal_token: Token![=] = content.parse()?;
+            let v = value(&content.parse()?)?;
+            match attr.to_string().as_str() {
+                "serialize" => renamed.serialize = Some(v),
+                "deserialize" => renamed.deserialize = Some(v),
+                other => {
+                    return Err(syn::Error::new(
+                        attr.span(),
+                        format!("expected `serialize` or `deserialize`, found `{}`", other),
+                    ));
+                }
+            }
+            if !content.is_empty() {
+                let _comma_token: Token![,] = content.parse()?;
+            }
+        }
+        Ok(renamed)
+    }
+}
+
+/// The case convention of `rename_all`, following the rules of serde
+#[derive(Debug, Clone, Copy)]
 pub(crate) enum RenameAll {
     None,
-    CamelCase,
     Lowercase,
+    Uppercase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
 }
 
-fn capitalize(field: &str) -> String {
-    let mut chars = field.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+impl RenameAll {
+    fn parse(name: &LitStr) -> syn::Result<Self> {
+        match name.value().as_str() {
+            "lowercase" => Ok(RenameAll::Lowercase),
+            "UPPERCASE" => Ok(RenameAll::Uppercase),
+            "PascalCase" => Ok(RenameAll::PascalCase),
+            "camelCase" => Ok(RenameAll::CamelCase),
+            "snake_case" => Ok(RenameAll::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameAll::ScreamingSnakeCase),
+            "kebab-case" => Ok(RenameAll::KebabCase),
+            "SCREAMING-KEBAB-CASE" => Ok(RenameAll::ScreamingKebabCase),
+            other => Err(syn::Error::new(
+                name.span(),
+                format!("unexpected rename_all type `{:?}`", other),
+            )),
+        }
     }
-}
 
-impl RenameAll {
-    fn apply(&self, field: &str) -> String {
+    /// Renames a field, fields are expected to be `snake_case`
+    fn apply_to_field(&self, field: &str) -> String {
+        match self {
+            RenameAll::None | RenameAll::Lowercase | RenameAll::SnakeCase => String::from(field),
+            RenameAll::Uppercase | RenameAll::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameAll::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameAll::CamelCase => {
+                let pascal = RenameAll::PascalCase.apply_to_field(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    None => String::new(),
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                }
+            }
+            RenameAll::KebabCase => field.replace('_', "-"),
+            RenameAll::ScreamingKebabCase => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+
+    /// Renames a variant, variants are expected to be `PascalCase`, every
+    /// uppercase letter starts a new word so `HTTPRequest` is `h_t_t_p_request` in `snake_case`
+    fn apply_to_variant(&self, variant: &str) -> String {
         match self {
-            RenameAll::None => String::from(field),
-            RenameAll::Lowercase => field.to_lowercase(),
+            RenameAll::None | RenameAll::PascalCase => String::from(variant),
+            RenameAll::Lowercase => variant.to_ascii_lowercase(),
+            RenameAll::Uppercase => variant.to_ascii_uppercase(),
             RenameAll::CamelCase => {
-                let mut parts = field.split('_');
-                let first = parts.next().expect("zero length name");
-                format!(
-                    "{}{}",
-                    first,
-                    parts.map(capitalize).collect::<Vec<String>>().join("")
-                )
+                let mut chars = variant.chars();
+                match chars.next() {
+                    None => String::new(),
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                }
+            }
+            RenameAll::SnakeCase => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
             }
+            RenameAll::ScreamingSnakeCase => RenameAll::SnakeCase
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            RenameAll::KebabCase => RenameAll::SnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            RenameAll::ScreamingKebabCase => RenameAll::ScreamingSnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default)]
 pub(crate) struct StructAttrs {
-    rename_all: RenameAll,
+    rename_all: Directional<RenameAll>,
+    rename_all_fields: Directional<RenameAll>,
     deny_unknown_fields: bool,
-    default: bool,
+    default: Option<DefaultValue>,
+    tag: Option<String>,
+    content: Option<String>,
+    untagged: bool,
+    unit_as_object: bool,
+    transparent: Option<Span>,
+    from: Option<Type>,
+    try_from: Option<Type>,
+    into: Option<Type>,
+    bound: Directional<Vec<WherePredicate>>,
+    repr: Option<Type>,
+    skip_serializing_none: bool,
+    remote: Option<Path>,
+    krate: Option<Path>,
 }
 
-impl Default for StructAttrs {
-    fn default() -> Self {
-        StructAttrs {
-            rename_all: RenameAll::None,
-            deny_unknown_fields: false,
-            default: false,
-        }
-    }
+/// How enum variants are represented
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Tagging<'attrs> {
+    /// `{"Variant": ...}`
+    External,
+    /// `{"tag": "Variant", ...}`
+    Internal { tag: &'attrs str },
+    /// `{"tag": "Variant", "content": ...}`
+    Adjacent {
+        tag: &'attrs str,
+        content: &'attrs str,
+    },
+    /// Only the payload, the first variant that matches is used when reading
+    Untagged,
 }
 
 impl Parse for StructAttrs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut rename_all = RenameAll::None;
+        let mut rename_all = Directional::default();
+        let mut rename_all_fields = Directional::default();
         let mut deny_unknown_fields = false;
-        let mut default = false;
+        let mut default = None;
+        let mut tag = None;
+        let mut content = None;
+        let mut untagged = None;
+        let mut unit_as_object = false;
+        let mut transparent = None;
+        let mut from = None;
+        let mut try_from = None;
+        let mut into = None;
+        let mut bound = Directional::default();
+        let mut repr = None;
+        let mut skip_serializing_none = false;
+        let mut remote = None;
+        let mut krate = None;
         while !input.is_empty() {
-            let attr: Ident = input.parse()?;
+            // `crate` is a keyword so it is not parsed by `Ident::parse`
+            let attr = Ident::parse_any(input)?;
             match attr.to_string().as_str() {
                 "rename_all" => {
-                    let _equal_token: Token![=] = input.parse()?;
-                    let name: Literal = input.parse()?;
-
-                    match name.to_string().as_str() {
-                        r#""camelCase""# => rename_all = RenameAll::CamelCase,
-                        r#""lowercase""# => rename_all = RenameAll::Lowercase,
-                        other => {
-                            return Err(syn::Error::new(
-                                attr.span(),
-                                format!("unexpected rename_all type `{}`", other),
-                            ));
-                        }
-                    }
+                    rename_all = Directional::parse(input, RenameAll::parse)?;
+                }
+                "rename_all_fields" => {
+                    rename_all_fields = Directional::parse(input, RenameAll::parse)?;
                 }
                 "deny_unknown_fields" => {
                     deny_unknown_fields = true;
                 }
                 "default" => {
-                    default = true;
+                    default = Some(DefaultValue::parse(input)?);
+                }
+                "tag" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let name: LitStr = input.parse()?;
+
+                    tag = Some(name.value());
+                }
+                "content" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let name: LitStr = input.parse()?;
+
+                    content = Some((name.value(), attr.span()));
+                }
+                "untagged" => {
+                    untagged = Some(attr.span());
+                }
+                "unit_as_object" => {
+                    unit_as_object = true;
+                }
+                "skip_serializing_none" => {
+                    skip_serializing_none = true;
+                }
+                "transparent" => {
+                    transparent = Some(attr.span());
+                }
+                "from" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let ty: LitStr = input.parse()?;
+
+                    from = Some((ty.parse()?, attr.span()));
+                }
+                "try_from" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let ty: LitStr = input.parse()?;
+
+                    try_from = Some(ty.parse()?);
+                }
+                "bound" => {
+                    bound = Directional::parse(input, |predicates| {
+                        let predicates = predicates.parse_with(
+                            Punctuated::<WherePredicate, Token![,]>::parse_terminated,
+                        )?;
+                        Ok(predicates.into_iter().collect())
+                    })?;
+                }
+                "crate" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let path: LitStr = input.parse()?;
+
+                    krate = Some(path.parse()?);
+                }
+                "remote" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let path: LitStr = input.parse()?;
+
+                    remote = Some(path.parse()?);
+                }
+                "repr" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let ty: LitStr = input.parse()?;
+
+                    repr = Some(ty.parse()?);
+                }
+                "into" => {
+                    let _equal_token: Token![=] = input.parse()?;
+                    let ty: LitStr = input.parse()?;
+
+                    into = Some(ty.parse()?);
                 }
                 other => {
                     return Err(syn::Error::new(
                         attr.span(),
-                        format!("unexpected field attribute `{}`", other),
+                        format!("unexpected container attribute `{}`", other),
                     ));
                 }
             }
@@ -174,27 +556,90 @@ impl Parse for StructAttrs {
                 let _comma_token: Token![,] = input.parse()?;
             }
         }
+        let content = match (&tag, content) {
+            (None, Some((_, span))) => {
+                return Err(syn::Error::new(span, "`content` requires `tag` to be set"));
+            }
+            (_, content) => content.map(|(content, _)| content),
+        };
+        if let (Some(_), Some(span)) = (&tag, untagged) {
+            return Err(syn::Error::new(
+                span,
+                "`untagged` can not be combined with `tag`",
+            ));
+        }
+        let from = match (from, &try_from) {
+            (Some((_, span)), Some(_)) => {
+                return Err(syn::Error::new(
+                    span,
+                    "`from` can not be combined with `try_from`",
+                ));
+            }
+            (from, _) => from.map(|(from, _)| from),
+        };
         Ok(StructAttrs {
             rename_all,
+            rename_all_fields,
             deny_unknown_fields,
             default,
+            tag,
+            content,
+            untagged: untagged.is_some(),
+            unit_as_object,
+            transparent,
+            from,
+            try_from,
+            into,
+            bound,
+            repr,
+            skip_serializing_none,
+            remote,
+            krate,
         })
     }
 }
 
-pub fn field_attrs(attr: &Attribute) -> FieldAttrs {
+pub fn field_attrs(attr: &Attribute) -> syn::Result<FieldAttrs> {
     attr.parse_args::<FieldAttrs>()
-        .expect("failed to parse attributes")
 }
 
-pub(crate) fn variant_attrs(attr: &Attribute) -> VariantAttrs {
+pub(crate) fn variant_attrs(attr: &Attribute) -> syn::Result<VariantAttrs> {
     attr.parse_args::<VariantAttrs>()
-        .expect("failed to parse attributes")
 }
 
-pub fn struct_attrs(attr: &Attribute) -> StructAttrs {
+pub fn struct_attrs(attr: &Attribute) -> syn::Result<StructAttrs> {
     attr.parse_args::<StructAttrs>()
-        .expect("failed to parse attributes")
+}
+
+/// The attribute of a field or variant that is used, `simd_json` takes precedence over `serde`
+fn attr_of(attrs: &[Attribute]) -> Option<&Attribute> {
+    get_attr(attrs, "simd_json").or_else(|| get_attr(attrs, "serde"))
+}
+
+/// The attributes of a field, they are checked by `StructAttrs::parse` so errors are ignored
+fn attrs_of(field: &Field) -> Option<FieldAttrs> {
+    attr_of(&field.attrs).and_then(|a| field_attrs(a).ok())
+}
+
+/// The function `name` in the `with` module
+fn with_fn(mut module: Path, name: &str) -> Path {
+    module
+        .segments
+        .push(Ident::new(name, proc_macro2::Span::call_site()).into());
+    module
+}
+
+/// The attributes of a variant, they are checked by `StructAttrs::parse` so errors are ignored
+fn attrs_of_variant(variant: &Variant) -> Option<VariantAttrs> {
+    attr_of(&variant.attrs).and_then(|a| variant_attrs(a).ok())
+}
+
+fn field_ident(field: &Field) -> String {
+    field
+        .ident
+        .as_ref()
+        .expect("Field is missing ident")
+        .to_string()
 }
 
 pub fn get_attr<'field>(attrs: &'field [Attribute], name: &str) -> Option<&'field Attribute> {
@@ -204,79 +649,463 @@ pub fn get_attr<'field>(attrs: &'field [Attribute], name: &str) -> Option<&'fiel
 }
 
 impl StructAttrs {
-    pub(crate) fn parse(attrs: Vec<Attribute>) -> StructAttrs {
-        if let Some(attrs) = get_attr(&attrs, "simd_json") {
-            struct_attrs(attrs)
-        } else if let Some(attrs) = get_attr(&attrs, "serde") {
-            struct_attrs(attrs)
-        } else {
-            StructAttrs::default()
+    /// Parses the attributes of a container and checks the attributes of all its
+    /// fields and variants, so they can be read without handling errors later on
+    pub(crate) fn parse(attrs: Vec<Attribute>, data: &Data) -> syn::Result<StructAttrs> {
+        let attr = attr_of(&attrs);
+        let mut attrs = attr.map(struct_attrs).transpose()?.unwrap_or_default();
+        // in `#[serde(..)]` `crate` is the path of serde itself and `bound` is
+        // written for serde's traits
+        if attr.is_some_and(|a| a.path().is_ident("serde")) {
+            attrs.krate = None;
+            attrs.bound = Directional::default();
+        }
+
+        let mut errors = Vec::new();
+        if let (Some(span), Data::Enum(_) | Data::Union(_)) = (attrs.transparent, data) {
+            errors.push(syn::Error::new(
+                span,
+                "`transparent` is only supported on structs",
+            ));
+        }
+        match (&attrs.repr, data) {
+            (Some(repr), Data::Struct(_) | Data::Union(_)) => {
+                errors.push(syn::Error::new_spanned(
+                    repr,
+                    "`repr` is only supported on enums",
+                ));
+            }
+            (Some(_), Data::Enum(data)) => {
+                for v in data.variants.iter().filter(|v| !v.fields.is_empty()) {
+                    errors.push(syn::Error::new_spanned(
+                        v,
+                        "`repr` requires all variants to be without fields",
+                    ));
+                }
+            }
+            (None, _) => (),
+        }
+        let fields: Vec<&Field> = match data {
+            Data::Struct(data) => {
+                if let Fields::Unnamed(fields) = &data.fields {
+                    if fields.unnamed.is_empty() {
+                        errors.push(syn::Error::new_spanned(
+                            fields,
+                            "tuple structs without fields are not supported, use a unit struct",
+                        ));
+                    }
+                }
+                data.fields.iter().collect()
+            }
+            Data::Enum(data) => {
+                for v in &data.variants {
+                    if let Some(Err(e)) = attr_of(&v.attrs).map(variant_attrs) {
+                        errors.push(e);
+                    }
+                    if !matches!(v.fields, Fields::Unit) && v.fields.is_empty() {
+                        errors.push(syn::Error::new_spanned(
+                            &v.fields,
+                            "variants without fields are not supported, use a unit variant",
+                        ));
+                    }
+                }
+                data.variants.iter().flat_map(|v| &v.fields).collect()
+            }
+            Data::Union(data) => data.fields.named.iter().collect(),
+        };
+        for f in fields {
+            // tuple fields are written by position, so leaving one out would shift the
+            // others, only transparent structs read and write just one of them
+            let skipped = attr_of(&f.attrs).filter(|a| {
+                field_attrs(a).is_ok_and(|a| a.skip || a.skip_serializing || a.skip_deserializing)
+            });
+            if let (None, None, Some(attr)) = (&f.ident, attrs.transparent, skipped) {
+                errors.push(syn::Error::new_spanned(
+                    attr,
+                    "`skip`, `skip_serializing` and `skip_deserializing` are not supported on tuple fields",
+                ));
+            }
+            match attr_of(&f.attrs).map(field_attrs) {
+                Some(Err(e)) => errors.push(e),
+                Some(Ok(FieldAttrs {
+                    getter: Some(getter),
+                    ..
+                })) if attrs.remote.is_none() || !matches!(data, Data::Struct(_)) => {
+                    errors.push(syn::Error::new_spanned(
+                        getter,
+                        "`getter` is only supported on fields of `remote` structs",
+                    ));
+                }
+                Some(Ok(FieldAttrs {
+                    borrow: Some(borrowed),
+                    ..
+                })) => {
+                    let lifetimes = lifetimes_of(&f.ty);
+                    if lifetimes.is_empty() {
+                        errors.push(syn::Error::new_spanned(
+                            &f.ty,
+                            "field has no lifetimes to borrow",
+                        ));
+                    }
+                    for l in borrowed.iter().filter(|l| !lifetimes.contains(l)) {
+                        errors.push(syn::Error::new_spanned(
+                            l,
+                            format!("field does not use lifetime `{}`", l),
+                        ));
+                    }
+                }
+                Some(Ok(_)) | None => (),
+            }
+        }
+        match errors.into_iter().reduce(|mut all, e| {
+            all.combine(e);
+            all
+        }) {
+            Some(e) => Err(e),
+            None => Ok(attrs),
         }
     }
     pub(crate) fn deny_unknown_fields(&self) -> bool {
         self.deny_unknown_fields
     }
 
+    /// If unit structs are written as `{}` instead of `null`
+    pub(crate) fn unit_as_object(&self) -> bool {
+        self.unit_as_object
+    }
+
+    /// The field a `transparent` struct is written as and read from, skipped fields and
+    /// fields of `PhantomData` are ignored, `None` if the struct isn't transparent
+    pub(crate) fn transparent_field<'fields>(
+        &self,
+        fields: &'fields Fields,
+    ) -> Option<syn::Result<(Member, &'fields Field)>> {
+        self.transparent?;
+        let mut candidates = fields.iter().zip(fields.members()).filter(|(f, _)| {
+            let phantom = match &f.ty {
+                Type::Path(TypePath { path, .. }) => path
+                    .segments
+                    .last()
+                    .is_some_and(|s| s.ident == "PhantomData"),
+                _ => false,
+            };
+            !phantom && !self.skip_serializing(f) && !self.skip_deserializing(f)
+        });
+        Some(match (candidates.next(), candidates.next()) {
+            (Some((field, member)), None) => Ok((member, field)),
+            _ => Err(syn::Error::new_spanned(
+                fields,
+                "`transparent` requires exactly one field that is neither skipped nor `PhantomData`",
+            )),
+        })
+    }
+
+    /// The path of `simd_json_derive` the generated code refers to, `crate = "path"`
+    /// sets it for crates that re-export the derives
+    pub(crate) fn crate_path(&self) -> Path {
+        self.krate
+            .clone()
+            .unwrap_or_else(|| parse_quote! { ::simd_json_derive })
+    }
+
+    /// The type from another crate `remote` derives the impls for
+    pub(crate) fn remote(&self) -> Option<&Path> {
+        self.remote.as_ref()
+    }
+
+    /// The type the impls are for, the `remote` type if set and `ident` otherwise
+    pub(crate) fn self_ty(&self, ident: &Ident) -> TokenStream2 {
+        match &self.remote {
+            Some(remote) => quote! { #remote },
+            None => quote! { #ident },
+        }
+    }
+
+    /// The trait the serialize impl is for, for `remote` types this is the local
+    /// trait `remote::serialize` declares as the real one can't be implemented for them
+    pub(crate) fn serialize_trait(&self) -> TokenStream2 {
+        let krate = self.crate_path();
+        match &self.remote {
+            Some(_) => quote! { __RemoteSerialize },
+            None => quote! { #krate::Serialize },
+        }
+    }
+
+    /// The trait the deserialize impl is for, see `serialize_trait`
+    pub(crate) fn deserialize_trait(&self) -> TokenStream2 {
+        let krate = self.crate_path();
+        match &self.remote {
+            Some(_) => quote! { __RemoteDeserialize },
+            None => quote! { #krate::Deserialize },
+        }
+    }
+
+    /// A reference to the value of a field of `self`, for fields of remote types
+    /// with a `getter` it is the value the getter returns
+    pub(crate) fn field_ref(&self, field: &Field, member: &Member) -> TokenStream2 {
+        match attrs_of(field).and_then(|a| a.getter) {
+            Some(getter) => quote! { &#getter(self) },
+            None => quote! { &self.#member },
+        }
+    }
+
+    /// The integer type the discriminants of an enum are written as with `repr`
+    pub(crate) fn repr(&self) -> Option<&Type> {
+        self.repr.as_ref()
+    }
+
+    /// Checks at compile time that the discriminant of every variant fits into the
+    /// `repr` type, `as` would silently truncate it otherwise
+    pub(crate) fn repr_checks(&self, ident: &Ident, data: &DataEnum) -> TokenStream2 {
+        let Some(repr) = &self.repr else {
+            return quote! {};
+        };
+        let checks = data.variants.iter().map(|v| {
+            let variant = &v.ident;
+            let message = format!(
+                "the discriminant of `{}::{}` does not fit into `{}`",
+                ident,
+                variant,
+                quote! { #repr }
+            );
+            quote_spanned! {v.span()=>
+                const _: () = ::std::assert!(
+                    #ident::#variant as i128 == (#ident::#variant as #repr) as i128,
+                    #message
+                );
+            }
+        });
+        quote! { #(#checks)* }
+    }
+
+    /// The `bound` replacing the inferred bounds of the `Serialize` impl
+    pub(crate) fn serialize_bound(&self) -> Option<&[WherePredicate]> {
+        self.bound.serialize.as_deref()
+    }
+
+    /// The `bound` replacing the inferred bounds of the `Deserialize` impl
+    pub(crate) fn deserialize_bound(&self) -> Option<&[WherePredicate]> {
+        self.bound.deserialize.as_deref()
+    }
+
+    /// The type that is read and converted with `From`, and if the conversion
+    /// can fail with `TryFrom`, instead of reading the type itself
+    pub(crate) fn converted_from(&self) -> Option<(&Type, bool)> {
+        match (&self.from, &self.try_from) {
+            (Some(from), _) => Some((from, false)),
+            (None, Some(try_from)) => Some((try_from, true)),
+            (None, None) => None,
+        }
+    }
+
+    /// The type a clone is converted into with `Into` and written instead of the type itself
+    pub(crate) fn converted_into(&self) -> Option<&Type> {
+        self.into.as_ref()
+    }
+
+    pub(crate) fn tagging(&self) -> Tagging<'_> {
+        match (&self.tag, &self.content) {
+            (Some(tag), Some(content)) => Tagging::Adjacent { tag, content },
+            (Some(tag), None) => Tagging::Internal { tag },
+            (None, _) if self.untagged => Tagging::Untagged,
+            (None, _) => Tagging::External,
+        }
+    }
+
+    /// The function deciding if a field is left out, with `skip_serializing_none`
+    /// this is `Option::is_none` for optional fields that don't set their own
     pub(crate) fn skip_serializing_if(&self, field: &Field) -> Option<Path> {
-        get_attr(&field.attrs, "simd_json")
-            .or_else(|| get_attr(&field.attrs, "serde"))
-            .map(field_attrs)
+        attrs_of(field)
             .and_then(|a| a.skip_serializing_if)
+            .or_else(|| {
+                (self.skip_serializing_none && self.optional(field).is_some())
+                    .then(|| parse_quote! { ::std::option::Option::is_none })
+            })
     }
-    
-    pub(crate) fn name_field(&self, field: &Field) -> String {
-        if let Some(attr) = get_attr(&field.attrs, "simd_json")
-            .map(field_attrs)
-            .and_then(|a| a.rename)
-        {
-            format!("{}:", OwnedValue::from(attr).encode())
-        } else if let Some(attr) = get_attr(&field.attrs, "serde")
-            .map(field_attrs)
-            .and_then(|a| a.rename)
-        {
-            format!("{}:", OwnedValue::from(attr).encode())
+
+    /// If the keys of the field are written inline into the enclosing object
+    pub(crate) fn flatten(&self, field: &Field) -> bool {
+        attrs_of(field).is_some_and(|a| a.flatten)
+    }
+
+    /// If the field may be missing from the input, this is the case for `Option`
+    /// fields and fields marked `optional`, like `Option` type aliases
+    pub(crate) fn optional<'field>(&self, field: &'field Field) -> Option<Optional<'field>> {
+        if let Some(inner) = option_inner(&field.ty) {
+            if option_inner(inner).is_some() && self.deserialize_with(field).is_none() {
+                Some(Optional::Double(inner))
+            } else {
+                Some(Optional::Option)
+            }
+        } else if attrs_of(field).is_some_and(|a| a.optional) {
+            Some(Optional::Option)
         } else {
-            let f = field
-                .ident
-                .as_ref()
-                .expect("Field is missing ident")
-                .to_string();
-            format!("{}:", OwnedValue::from(self.rename_all.apply(&f)).encode())
+            None
+        }
+    }
+
+    /// The lifetimes of a `borrow` field that are borrowed from the input
+    pub(crate) fn borrowed_lifetimes(&self, field: &Field) -> Vec<Lifetime> {
+        match attrs_of(field).and_then(|a| a.borrow) {
+            Some(lifetimes) if lifetimes.is_empty() => lifetimes_of(&field.ty),
+            Some(lifetimes) => lifetimes,
+            None => Vec::new(),
         }
     }
 
+    /// If the field collects all keys that are not claimed by any other field
+    pub(crate) fn extra(&self, field: &Field) -> bool {
+        attrs_of(field).is_some_and(|a| a.extra)
+    }
+
+    /// If the field is never written
+    pub(crate) fn skip_serializing(&self, field: &Field) -> bool {
+        attrs_of(field).is_some_and(|a| a.skip || a.skip_serializing)
+    }
+
+    /// If the field is never read and filled from its default instead
+    pub(crate) fn skip_deserializing(&self, field: &Field) -> bool {
+        attrs_of(field).is_some_and(|a| a.skip || a.skip_deserializing)
+    }
+
+    /// The function writing the field instead of its `Serialize` impl,
+    /// `serialize_with` or `json_write` in the `with` module
+    pub(crate) fn serialize_with(&self, field: &Field) -> Option<Path> {
+        let attrs = attrs_of(field)?;
+        attrs
+            .serialize_with
+            .or_else(|| Some(with_fn(attrs.with?, "json_write")))
+    }
+
+    /// The function reading the field instead of its `Deserialize` impl,
+    /// `deserialize_with` or `from_tape` in the `with` module
+    pub(crate) fn deserialize_with(&self, field: &Field) -> Option<Path> {
+        let attrs = attrs_of(field)?;
+        attrs
+            .deserialize_with
+            .or_else(|| Some(with_fn(attrs.with?, "from_tape")))
+    }
+
+    /// The other names a field is read from
+    pub(crate) fn field_aliases(&self, field: &Field) -> Vec<String> {
+        attrs_of(field).map(|a| a.aliases).unwrap_or_default()
+    }
+
+    /// The variant unknown variants are read into, it either has no fields or
+    /// a single unnamed field the name of the unknown variant is kept in
+    pub(crate) fn other_variant<'data>(
+        &self,
+        data: &'data DataEnum,
+    ) -> syn::Result<Option<&'data Variant>> {
+        let mut others = data
+            .variants
+            .iter()
+            .filter(|v| attrs_of_variant(v).is_some_and(|a| a.other));
+        let Some(other) = others.next() else {
+            return Ok(None);
+        };
+        if let Some(second) = others.next() {
+            return Err(syn::Error::new_spanned(
+                second,
+                "only one variant can be marked as `other`",
+            ));
+        }
+        if self.untagged {
+            return Err(syn::Error::new_spanned(
+                other,
+                "`other` is not supported on untagged enums",
+            ));
+        }
+        match &other.fields {
+            Fields::Unit => Ok(Some(other)),
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(Some(other)),
+            _ => Err(syn::Error::new_spanned(
+                other,
+                "`other` requires a variant without fields or with a single unnamed field",
+            )),
+        }
+    }
+
+    /// The other names a variant is read from
+    pub(crate) fn variant_aliases(&self, variant: &Variant) -> Vec<String> {
+        attrs_of_variant(variant)
+            .map(|a| a.aliases)
+            .unwrap_or_default()
+    }
+
+    /// The attributes for the fields of the struct variants of an enum, those
+    /// are renamed by `rename_all_fields` instead of `rename_all`
+    pub(crate) fn variant_fields(&self) -> StructAttrs {
+        StructAttrs {
+            rename_all: self.rename_all_fields.clone(),
+            rename_all_fields: Directional::default(),
+            ..self.clone()
+        }
+    }
+
+    /// The key a field is written with, encoded and followed by a `:`
+    pub(crate) fn name_field(&self, field: &Field) -> String {
+        let name = attrs_of(field)
+            .and_then(|a| a.rename.serialize)
+            .unwrap_or_else(|| {
+                let rule = self.rename_all.serialize.unwrap_or(RenameAll::None);
+                rule.apply_to_field(&field_ident(field))
+            });
+        format!("{}:", OwnedValue::from(name).encode())
+    }
+
+    /// The key a field is read from
+    pub(crate) fn deserialize_name_field(&self, field: &Field) -> String {
+        attrs_of(field)
+            .and_then(|a| a.rename.deserialize)
+            .unwrap_or_else(|| {
+                let rule = self.rename_all.deserialize.unwrap_or(RenameAll::None);
+                rule.apply_to_field(&field_ident(field))
+            })
+    }
+
+    /// The name a variant is written with
     pub(crate) fn name_variant(&self, variant: &Variant) -> String {
-        if let Some(attr) = get_attr(&variant.attrs, "simd_json")
-            .map(variant_attrs)
-            .and_then(|a| a.rename)
-        {
-            format!("{}", attr)
-        } else if let Some(attr) = get_attr(&variant.attrs, "serde")
-            .map(variant_attrs)
-            .and_then(|a| a.rename)
-        {
-            format!("{}", attr)
-        } else {
-            let v = variant.ident.to_string();
-            format!("{}", self.rename_all.apply(&v))
+        attrs_of_variant(variant)
+            .and_then(|a| a.rename.serialize)
+            .unwrap_or_else(|| {
+                let rule = self.rename_all.serialize.unwrap_or(RenameAll::None);
+                rule.apply_to_variant(&variant.ident.to_string())
+            })
+    }
+
+    /// The name a variant is read from
+    pub(crate) fn deserialize_name_variant(&self, variant: &Variant) -> String {
+        attrs_of_variant(variant)
+            .and_then(|a| a.rename.deserialize)
+            .unwrap_or_else(|| {
+                let rule = self.rename_all.deserialize.unwrap_or(RenameAll::None);
+                rule.apply_to_variant(&variant.ident.to_string())
+            })
+    }
+
+    /// If the container has a `default`, this is the expression creating the value
+    /// missing fields are taken from
+    pub(crate) fn container_default(&self) -> Option<TokenStream2> {
+        match &self.default {
+            Some(DefaultValue::Trait) => {
+                Some(quote! { <Self as ::std::default::Default>::default() })
+            }
+            Some(DefaultValue::Path(path)) => Some(quote! { #path() }),
+            None => None,
         }
     }
-    
-    pub(crate) fn default_fallback(&self, field: &Field) -> bool {
-        if self.default {
-            true
-        } else if let Some(attr) = get_attr(&field.attrs, "simd_json")
-            .map(field_attrs)
-            .and_then(|a| Some(a.default))
-        {
-            attr
-        } else if let Some(attr) = get_attr(&field.attrs, "serde")
-            .map(field_attrs)
-            .and_then(|a| Some(a.default))
-        {
-            attr
-        } else {
-            false
+
+    /// The expression used for a missing field `member`, if the field has a default of its own
+    /// it takes precedence over the one of the container which is read from `__deser_default`
+    pub(crate) fn default_fallback(&self, field: &Field, member: &Member) -> Option<TokenStream2> {
+        match attrs_of(field).and_then(|a| a.default) {
+            Some(DefaultValue::Trait) => Some(quote! { ::std::default::Default::default() }),
+            Some(DefaultValue::Path(path)) => Some(quote! { #path() }),
+            None => self
+                .default
+                .as_ref()
+                .map(|_| quote! { __deser_default.#member }),
         }
     }
 }
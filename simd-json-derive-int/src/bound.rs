@@ -0,0 +1,168 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use std::collections::HashSet;
+use syn::{
+    parse_quote,
+    visit::{self, Visit},
+    Data, Field, GenericParam, Generics, Ident, Lifetime, LifetimeParam, TypePath, WherePredicate,
+};
+
+use crate::args::StructAttrs;
+
+/// The generics of a `Deserialize` impl
+#[derive(Clone)]
+pub(crate) struct DeserializeGenerics {
+    /// `<..>` of the impl, including the lifetime of the input
+    pub(crate) impl_generics: TokenStream2,
+    /// `<..>` of the type
+    pub(crate) ty_generics: TokenStream2,
+    /// The where clause with the bounds of the impl
+    pub(crate) where_clause: TokenStream2,
+    /// The lifetime of the input
+    pub(crate) lifetime: Lifetime,
+}
+
+/// The generics of a `Serialize` impl, every type parameter used by a field
+/// that is written is bound by `Serialize` unless `bound` is given
+pub(crate) fn serialize(attrs: &StructAttrs, data: &Data, generics: &Generics) -> Generics {
+    let krate = attrs.crate_path();
+    let fields = fields(data)
+        .into_iter()
+        .filter(|_| attrs.converted_into().is_none())
+        .filter(|f| !attrs.skip_serializing(f) && attrs.serialize_with(f).is_none());
+    with_bound(generics, fields, attrs.serialize_bound(), |param| {
+        parse_quote! { #param: #krate::Serialize }
+    })
+}
+
+/// The generics of a `Deserialize` impl, the first lifetime of the type is the lifetime
+/// of the input and outlives all other lifetimes, if there is none `'input` is added.
+/// Every type parameter used by a field that is read is bound by `Deserialize` unless `bound` is given
+pub(crate) fn deserialize(
+    attrs: &StructAttrs,
+    data: &Data,
+    generics: &Generics,
+) -> DeserializeGenerics {
+    let krate = attrs.crate_path();
+    let mut impl_generics = generics.clone();
+    let lifetime = if let Some(param) = generics.lifetimes().next() {
+        param.lifetime.clone()
+    } else {
+        let lifetime: Lifetime = parse_quote! { 'input };
+        impl_generics.params.insert(
+            0,
+            GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+        );
+        lifetime
+    };
+
+    let bound_fields = fields(data)
+        .into_iter()
+        .filter(|_| attrs.converted_from().is_none())
+        .filter(|f| !attrs.skip_deserializing(f) && attrs.deserialize_with(f).is_none());
+    let mut impl_generics = with_bound(
+        &impl_generics,
+        bound_fields,
+        attrs.deserialize_bound(),
+        |param| parse_quote! { #param: #krate::Deserialize<#lifetime> },
+    );
+    for param in generics.lifetimes().skip(1) {
+        let other = &param.lifetime;
+        impl_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { #lifetime: #other });
+    }
+    // lifetimes of `borrow` fields live exactly as long as the input
+    let mut borrowed: Vec<Lifetime> = Vec::new();
+    for f in fields(data)
+        .into_iter()
+        .filter(|f| !attrs.skip_deserializing(f))
+    {
+        for other in attrs.borrowed_lifetimes(f) {
+            if other != lifetime && !borrowed.contains(&other) {
+                impl_generics
+                    .make_where_clause()
+                    .predicates
+                    .push(parse_quote! { #other: #lifetime });
+                borrowed.push(other);
+            }
+        }
+    }
+
+    let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+    let (_, ty_generics, _) = generics.split_for_impl();
+    DeserializeGenerics {
+        impl_generics: quote! { #impl_generics },
+        ty_generics: quote! { #ty_generics },
+        where_clause: quote! { #where_clause },
+        lifetime,
+    }
+}
+
+/// The fields of a struct or of all variants of an enum
+fn fields(data: &Data) -> Vec<&Field> {
+    match data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(data) => data.fields.named.iter().collect(),
+    }
+}
+
+/// Adds `bound` for every type parameter used in one of `fields`, or the
+/// predicates of the `bound` attribute instead if there are any
+fn with_bound<'field>(
+    generics: &Generics,
+    fields: impl Iterator<Item = &'field Field>,
+    custom: Option<&[WherePredicate]>,
+    bound: impl Fn(&Ident) -> WherePredicate,
+) -> Generics {
+    let predicates: Vec<WherePredicate> = if let Some(custom) = custom {
+        custom.to_vec()
+    } else {
+        let mut visitor = TypeParams {
+            params: generics.type_params().map(|p| &p.ident).collect(),
+            used: HashSet::new(),
+        };
+        for f in fields {
+            visitor.visit_type(&f.ty);
+        }
+        generics
+            .type_params()
+            .filter(|p| visitor.used.contains(&p.ident))
+            .map(|p| bound(&p.ident))
+            .collect()
+    };
+    let mut generics = generics.clone();
+    if !predicates.is_empty() {
+        generics.make_where_clause().predicates.extend(predicates);
+    }
+    generics
+}
+
+/// Collects the type parameters a type refers to, those only used in `PhantomData` don't count
+struct TypeParams<'params> {
+    params: HashSet<&'params Ident>,
+    used: HashSet<Ident>,
+}
+
+impl<'ast> Visit<'ast> for TypeParams<'_> {
+    fn visit_type_path(&mut self, ty: &'ast TypePath) {
+        if ty
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "PhantomData")
+        {
+            return;
+        }
+        if let (None, None, Some(first)) =
+            (&ty.qself, &ty.path.leading_colon, ty.path.segments.first())
+        {
+            if self.params.contains(&first.ident) {
+                self.used.insert(first.ident.clone());
+            }
+        }
+        visit::visit_type_path(self, ty);
+    }
+}
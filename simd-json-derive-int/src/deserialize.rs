@@ -2,29 +2,51 @@ use proc_macro::{self, TokenStream};
 use syn::{parse_macro_input, Data, DeriveInput};
 
 use crate::args::*;
+use crate::{bound, remote};
 
 mod r#struct;
 
 mod r#enum;
 
+mod from;
+
+mod repr;
+
 pub(crate) fn derive(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    match input {
-        // struct
-        DeriveInput {
-            ident,
-            attrs,
-            data: Data::Struct(defn),
-            generics,
-            ..
-        } => r#struct::derive(StructAttrs::parse(attrs), ident, generics, defn), // Named
-        DeriveInput {
-            ident,
-            attrs,
-            data: Data::Enum(defn),
-            generics,
-            ..
-        } => r#enum::derive(StructAttrs::parse(attrs), ident, generics, defn),
-        _ => unimplemented!("This was trying to derive something odd"),
+    let DeriveInput {
+        ident,
+        attrs,
+        data,
+        generics,
+        ..
+    } = parse_macro_input!(input as DeriveInput);
+    let attrs = match StructAttrs::parse(attrs, &data) {
+        Ok(attrs) => attrs,
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
+    let generics = bound::deserialize(&attrs, &data, &generics);
+    let krate = attrs.crate_path();
+    let remote = attrs.remote().map(|remote| (remote.clone(), data.clone()));
+    let derived = if let Some((from, fallible)) = attrs.converted_from() {
+        from::derive(&attrs, from, fallible, ident.clone(), generics.clone())
+    } else {
+        match data {
+            // struct
+            Data::Struct(defn) => r#struct::derive(attrs, ident.clone(), generics.clone(), defn),
+            Data::Enum(defn) => match attrs.repr() {
+                Some(repr) => repr::derive(&attrs, repr, ident.clone(), generics.clone(), defn),
+                None => r#enum::derive(attrs, ident.clone(), generics.clone(), defn),
+            },
+            Data::Union(data) => TokenStream::from(
+                syn::Error::new_spanned(data.union_token, "unions are not supported")
+                    .to_compile_error(),
+            ),
+        }
+    };
+    match remote {
+        Some((remote, data)) => {
+            remote::deserialize(&krate, &remote, &ident, &data, &generics, derived)
+        }
+        None => derived,
     }
 }
@@ -1,135 +1,250 @@
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::{format_ident, quote};
-use syn::{DataEnum, Fields, GenericParam, Generics, Variant};
+use syn::{DataEnum, Fields, Variant};
 
-use crate::args::StructAttrs;
+use crate::args::{StructAttrs, Tagging};
+use crate::bound::DeserializeGenerics;
 
 pub(super) fn derive(
     attrs: StructAttrs,
     ident: Ident,
-    generics: Generics,
+    generics: DeserializeGenerics,
     data: DataEnum,
 ) -> proc_macro::TokenStream {
-    let params = &generics.params;
-    let (all_generics, derive_lt) = match params.first() {
-        None => (quote! { <'input> }, quote! { 'input }),
-        Some(GenericParam::Lifetime(lifetime)) => (quote! { <#params> }, quote! { #lifetime }),
-        Some(_) => (quote! { <'input, #params> }, quote! { 'input }),
-    };
+    let krate = attrs.crate_path();
+    let deserialize_trait = attrs.deserialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let DeserializeGenerics {
+        impl_generics,
+        ty_generics,
+        where_clause,
+        lifetime: derive_lt,
+    } = generics;
 
-    // let mut body_elements = Vec::new();
-    let variants = data.variants;
-    let (simple, variants): (Vec<_>, Vec<_>) =
-        variants.into_iter().partition(|v| v.fields.is_empty());
-    let (named, unnamed): (Vec<_>, Vec<_>) = variants.iter().partition(|v| {
-        matches!(
-            v,
-            Variant {
-                fields: Fields::Named(_),
-                ..
-            }
-        )
-    });
-
-    let (unnamed1, unnamed): (Vec<_>, Vec<_>) =
-        unnamed.into_iter().partition(|v| v.fields.len() == 1);
-    if !named.is_empty() {
-        todo!(
-            "ENUM variants with named fields are not supported: {:?}",
-            named
+    if attrs.container_default().is_some() {
+        return TokenStream::from(
+            syn::Error::new_spanned(&ident, "`default` is only supported on structs")
+                .to_compile_error(),
         );
     }
-    let (unnamed_keys, unnamed_values): (Vec<_>, Vec<_>) = unnamed
-        .iter()
-        .map(|s| {
-            (
-                &s.ident,
-                (
-                    attrs.name_variant(&s),
-                    s.fields
-                        .iter()
-                        .enumerate()
-                        .map(|f| format_ident!("_unnamed_{}", f.0))
-                        .collect::<Vec<_>>(),
-                ),
-            )
-        })
-        .unzip();
-    let (unnamed_values, unnamed_fields): (Vec<_>, Vec<_>) = unnamed_values
-        .into_iter()
-        .map(|(v, f)| {
-            (
-                v,
-                (
-                    f.len(),
-                    quote! {
-                        #(
-                            {
-                                let #f = ::simd_json_derive::Deserialize::from_tape(__deser_tape)?;
-                                #f
-                            }
-                        ),*
-                    },
-                ),
-            )
-        })
-        .unzip();
-    let (unnamed_len, unnamed_fields): (Vec<_>, Vec<_>) = unnamed_fields.into_iter().unzip();
-    let unnamed = quote! {
-        #(
-            Some(::simd_json::Node::String(#unnamed_values)) => {
-                match __deser_tape.next() {
-                  Some(::simd_json::Node::Array{len: #unnamed_len, ..}) => Ok(#ident::#unnamed_keys(#unnamed_fields)),
-                  _ => Err(::simd_json_derive::de::Error::FieldNotAnArray(#unnamed_values))
-                }
 
-           },
-        )*
+    let tagging = attrs.tagging();
+    if let Tagging::Internal { .. } = tagging {
+        if let Some(v) = data
+            .variants
+            .iter()
+            .find(|v| matches!(v.fields, Fields::Unnamed(_)) && v.fields.len() > 1)
+        {
+            return TokenStream::from(
+                syn::Error::new_spanned(v, "internally tagged enums do not support tuple variants")
+                    .to_compile_error(),
+            );
+        }
+    }
+
+    // unknown variant names, bound to `__other`, are read into the `other` variant
+    let other = match attrs.other_variant(&data) {
+        Ok(other) => other,
+        Err(e) => return TokenStream::from(e.to_compile_error()),
     };
-    // unnamed 1
-    let (unnamed1_keys, unnamed1_values): (Vec<_>, Vec<_>) = unnamed1
-        .iter()
-        .map(|s| (&s.ident, attrs.name_variant(&s)))
-        .unzip();
-    let unnamed1 = quote! {
-        #(
-            Some(::simd_json::Node::String(#unnamed1_values)) => Ok(#ident::#unnamed1_keys(::simd_json_derive::Deserialize::from_tape(__deser_tape)?)),
-        )*
+    let unknown = other.map(|v| {
+        let variant_ident = &v.ident;
+        if v.fields.is_empty() {
+            quote! { Self::#variant_ident }
+        } else {
+            quote! { Self::#variant_ident(::std::convert::From::from(__other)) }
+        }
+    });
+
+    // Variants without fields are matched by name alone, every other variant is
+    // turned into its name and an expression reading the payload from `__deser_tape`,
+    // how the name is found depends on the tagging.
+    let mut simple_keys = Vec::new();
+    let mut simple_values = Vec::new();
+    let mut payload_values = Vec::new();
+    let mut payload_reads = Vec::new();
+    for v in &data.variants {
+        if other.is_some_and(|o| o.ident == v.ident && !v.fields.is_empty()) {
+            continue;
+        }
+        let name = attrs.deserialize_name_variant(v);
+        let aliases = attrs.variant_aliases(v);
+        let pattern = quote! { #name #(| #aliases)* };
+        if let Some(read) = payload(&attrs, v) {
+            payload_values.push(pattern);
+            payload_reads.push(read);
+        } else {
+            simple_keys.push(&v.ident);
+            simple_values.push(pattern);
+        }
+    }
+
+    let (unknown_name, unknown_key, unknown_tag) = if let Some(unknown) = unknown {
+        (
+            quote! {
+                Some(#krate::__private::Node::String(__other)) => Ok(#unknown),
+            },
+            quote! {
+                Some(#krate::__private::Node::String(__other)) => {
+                    #krate::__skip(1, __deser_tape);
+                    Ok(#unknown)
+                },
+            },
+            quote! { __other => Ok(#unknown), },
+        )
+    } else {
+        (
+            quote! {},
+            quote! {
+                Some(#krate::__private::Node::String(__other)) => Err(#krate::de::Error::UnknownEnumVariant(__other.to_string())),
+            },
+            quote! {
+                __other => Err(#krate::de::Error::UnknownEnumVariant(__other.to_string())),
+            },
+        )
     };
 
-    let (simple_keys, simple_values): (Vec<_>, Vec<_>) = simple
-        .iter()
-        .map(|s| (&s.ident, attrs.name_variant(&s)))
-        .unzip();
-    let simple = quote! {
-        #(
-            Some(::simd_json::Node::String(#simple_values)) => Ok(#ident::#simple_keys),
-        )*
+    let body = match tagging {
+        Tagging::External => quote! {
+            match __deser_tape.next() {
+                #(
+                    Some(#krate::__private::Node::String(#simple_values)) => Ok(Self::#simple_keys),
+                )*
+                #unknown_name
+                Some(#krate::__private::Node::Object{len: 1, ..}) => {
+                    match __deser_tape.next() {
+                        #(
+                            Some(#krate::__private::Node::String(#payload_values)) => {
+                                #payload_reads
+                            },
+                        )*
+                        #unknown_key
+                        Some(_) => Err(#krate::de::Error::InvalidEnumRepresentation),
+                        None => Err(#krate::de::Error::EOF)
+                    }
+                },
+                Some(__other) => Err(#krate::de::Error::InvalidEnumRepresentation),
+                None => Err(#krate::de::Error::EOF)
+            }
+        },
+        Tagging::Internal { tag } => quote! {
+            let (__deser_variant, mut __deser_inner) = #krate::__untag(__deser_tape, #tag)?;
+            let __deser_tape = &mut __deser_inner;
+            match __deser_variant {
+                #(
+                    #simple_values => Ok(Self::#simple_keys),
+                )*
+                #(
+                    #payload_values => {
+                        #payload_reads
+                    },
+                )*
+                #unknown_tag
+            }
+        },
+        Tagging::Adjacent { tag, content } => quote! {
+            let (__deser_variant, __deser_content) = #krate::__untag_adjacent(__deser_tape, #tag, #content)?;
+            match __deser_variant {
+                #(
+                    #simple_values => Ok(Self::#simple_keys),
+                )*
+                #(
+                    #payload_values => {
+                        let mut __deser_inner = __deser_content.ok_or(#krate::de::Error::MissingField(#content))?;
+                        let __deser_tape = &mut __deser_inner;
+                        #payload_reads
+                    },
+                )*
+                #unknown_tag
+            }
+        },
+        Tagging::Untagged => {
+            // every variant is tried in order against its own copy of the subtree
+            let reads = data.variants.iter().map(|v| {
+                let variant_ident = &v.ident;
+                payload(&attrs, v).unwrap_or_else(|| {
+                    quote! {
+                        match __deser_tape.next() {
+                            Some(#krate::__private::Node::Static(#krate::__private::StaticNode::Null)) => Ok(Self::#variant_ident),
+                            _ => Err(#krate::de::Error::expected_null()),
+                        }
+                    }
+                })
+            });
+            let error = format!("data did not match any variant of untagged enum {ident}");
+            quote! {
+                let __deser_nodes = #krate::__subtree(__deser_tape);
+                #(
+                    {
+                        let mut __deser_fork = __deser_nodes.clone().into_iter().peekable();
+                        let __deser_tape = &mut __deser_fork;
+                        let __deser_res = (|| -> #krate::de::Result<Self> { #reads })();
+                        if __deser_res.is_ok() {
+                            return __deser_res;
+                        }
+                    }
+                )*
+                Err(#krate::de::Error::custom(#error))
+            }
+        }
     };
     let expanded = quote! {
-        impl #all_generics ::simd_json_derive::Deserialize <#derive_lt> for #ident #generics {
+        impl #impl_generics #deserialize_trait <#derive_lt> for #self_ty #ty_generics #where_clause {
             #[inline]
-            fn from_tape(__deser_tape: &mut ::simd_json_derive::Tape<#derive_lt>) -> ::simd_json_derive::de::Result<Self>
+            fn from_tape(__deser_tape: &mut #krate::Tape<#derive_lt>) -> #krate::de::Result<Self>
             where
                 Self: std::marker::Sized + #derive_lt
             {
-                match __deser_tape.next() {
-                    #simple
-                    Some(::simd_json::Node::Object{len: 1, ..}) => {
-                        match __deser_tape.next() {
-                            #unnamed1
-                            #unnamed
-                            Some(::simd_json::Node::String(__other)) => Err(::simd_json_derive::de::Error::UnknownEnumVariant(__other.to_string()).into()),
-                            Some(_) => Err(::simd_json_derive::de::Error::InvalidEnumRepresentation),
-                            None => Err(::simd_json_derive::de::Error::EOF)
-                        }
-                    },
-                    Some(__other) => Err(::simd_json_derive::de::Error::InvalidEnumRepresentation),
-                    None => Err(::simd_json_derive::de::Error::EOF)
-                }
+                #body
             }
         }
     };
     TokenStream::from(expanded)
 }
+
+/// Reads the payload of a variant from `__deser_tape`, `None` for variants without fields
+fn payload(attrs: &StructAttrs, v: &Variant) -> Option<TokenStream2> {
+    let krate = attrs.crate_path();
+    let variant_ident = &v.ident;
+    match &v.fields {
+        Fields::Unit => None,
+        fields if fields.is_empty() => None,
+        // unnamed 1
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let read = super::r#struct::named::read_fn(attrs, &fields.unnamed[0]);
+            Some(quote! {
+                Ok(Self::#variant_ident(#read(__deser_tape)?))
+            })
+        }
+        // unnamed
+        Fields::Unnamed(unnamed) => {
+            let name = attrs.deserialize_name_variant(v);
+            let len = v.fields.len();
+            let fields = (0..len).map(|i| format_ident!("_unnamed_{}", i));
+            let reads = unnamed
+                .unnamed
+                .iter()
+                .map(|f| super::r#struct::named::read_fn(attrs, f));
+            Some(quote! {
+                match __deser_tape.next() {
+                    Some(#krate::__private::Node::Array{len: #len, ..}) => Ok(Self::#variant_ident(
+                        #(
+                            {
+                                let #fields = #reads(__deser_tape)?;
+                                #fields
+                            }
+                        ),*
+                    )),
+                    _ => Err(#krate::de::Error::FieldNotAnArray(#name))
+                }
+            })
+        }
+        // named
+        Fields::Named(_) => Some(super::r#struct::named::body(
+            &attrs.variant_fields(),
+            &quote! { Self::#variant_ident },
+            &v.fields,
+        )),
+    }
+}
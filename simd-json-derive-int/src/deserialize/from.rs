@@ -0,0 +1,47 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Ident, Type};
+
+use crate::{args::StructAttrs, bound::DeserializeGenerics};
+
+/// Any type with `from = "Type"` or `try_from = "Type"`, it is read as `Type` and converted
+pub(crate) fn derive(
+    attrs: &StructAttrs,
+    from: &Type,
+    fallible: bool,
+    ident: Ident,
+    generics: DeserializeGenerics,
+) -> TokenStream {
+    let krate = attrs.crate_path();
+    let deserialize_trait = attrs.deserialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let DeserializeGenerics {
+        impl_generics,
+        ty_generics,
+        where_clause,
+        lifetime: derive_lt,
+    } = generics;
+
+    let convert = if fallible {
+        quote! {
+            ::std::convert::TryFrom::try_from(v).map_err(#krate::de::Error::custom)
+        }
+    } else {
+        quote! {
+            Ok(::std::convert::From::from(v))
+        }
+    };
+    let expanded = quote! {
+        impl #impl_generics #deserialize_trait <#derive_lt> for #self_ty #ty_generics #where_clause {
+            #[inline]
+            fn from_tape(__deser_tape: &mut #krate::Tape<#derive_lt>) -> #krate::de::Result<Self>
+            where
+                Self: std::marker::Sized + #derive_lt
+            {
+                let v: #from = #krate::Deserialize::from_tape(__deser_tape)?;
+                #convert
+            }
+        }
+    };
+    TokenStream::from(expanded)
+}
@@ -0,0 +1,57 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{DataEnum, Ident, Type};
+
+use crate::{args::StructAttrs, bound::DeserializeGenerics};
+
+/// Enum with `repr = "int"`, read from the discriminant of its variants, unknown
+/// discriminants are read into the `other` variant if there is one
+pub(crate) fn derive(
+    attrs: &StructAttrs,
+    repr: &Type,
+    ident: Ident,
+    generics: DeserializeGenerics,
+    data: DataEnum,
+) -> TokenStream {
+    let krate = attrs.crate_path();
+    let deserialize_trait = attrs.deserialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let DeserializeGenerics {
+        impl_generics,
+        ty_generics,
+        where_clause,
+        lifetime: derive_lt,
+    } = generics;
+
+    let unknown = match attrs.other_variant(&data) {
+        Ok(Some(other)) => {
+            let other = &other.ident;
+            quote! { Ok(Self::#other) }
+        }
+        Ok(None) => quote! {
+            Err(#krate::de::Error::UnknownEnumVariant(__deser_value.to_string()))
+        },
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
+    let checks = attrs.repr_checks(&ident, &data);
+    let variants = data.variants.iter().map(|v| &v.ident);
+    let expanded = quote! {
+        #checks
+        impl #impl_generics #deserialize_trait <#derive_lt> for #self_ty #ty_generics #where_clause {
+            #[inline]
+            fn from_tape(__deser_tape: &mut #krate::Tape<#derive_lt>) -> #krate::de::Result<Self>
+            where
+                Self: std::marker::Sized + #derive_lt
+            {
+                let __deser_value: #repr = #krate::Deserialize::from_tape(__deser_tape)?;
+                #(
+                    if __deser_value == Self::#variants as #repr {
+                        return Ok(Self::#variants);
+                    }
+                )*
+                #unknown
+            }
+        }
+    };
+    TokenStream::from(expanded)
+}
@@ -1,16 +1,25 @@
 pub(super) mod named;
+mod transparent;
+pub(super) mod unit;
 pub(super) mod unnamed;
 
-use syn::{DataStruct, Fields, FieldsNamed, FieldsUnnamed, Generics, Ident};
+use syn::{DataStruct, Fields, FieldsNamed, FieldsUnnamed, Ident};
 
-use crate::args::StructAttrs;
+use crate::{args::StructAttrs, bound};
 
 pub(crate) fn derive(
     attrs: StructAttrs,
     ident: Ident,
-    generics: Generics,
+    generics: bound::DeserializeGenerics,
     defn: DataStruct,
 ) -> proc_macro::TokenStream {
+    match attrs.transparent_field(&defn.fields) {
+        Some(Ok((member, field))) => {
+            return transparent::derive(&attrs, ident, generics, &defn.fields, member, field)
+        }
+        Some(Err(e)) => return proc_macro::TokenStream::from(e.to_compile_error()),
+        None => (),
+    }
     match defn {
         DataStruct {
             fields: Fields::Unnamed(FieldsUnnamed { unnamed, .. }),
@@ -24,8 +33,6 @@ pub(crate) fn derive(
         DataStruct {
             fields: Fields::Unit,
             ..
-        } => {
-            todo!("Unit structs are not implemented")
-        }
+        } => unit::derive(attrs, ident, generics),
     }
 }
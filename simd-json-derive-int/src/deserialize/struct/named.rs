@@ -1,136 +1,299 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
-use syn::{
-    punctuated::Punctuated, token::Comma, Field, GenericParam, Generics, Ident, Path, PathSegment,
-    Type, TypePath,
-};
+use syn::{punctuated::Punctuated, token::Comma, Field, Ident, Member};
 
-use crate::args::StructAttrs;
+use crate::{
+    args::{Optional, StructAttrs},
+    bound::DeserializeGenerics,
+};
 
 /// Named struct as `Struct(u8)` or `Struct(u8, String)`
 pub(crate) fn derive(
     attrs: StructAttrs,
     ident: Ident,
-    generics: Generics,
+    generics: DeserializeGenerics,
     fields: Punctuated<Field, Comma>,
 ) -> proc_macro::TokenStream {
+    let krate = attrs.crate_path();
+    let deserialize_trait = attrs.deserialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let DeserializeGenerics {
+        impl_generics,
+        ty_generics,
+        where_clause,
+        lifetime: derive_lt,
+    } = generics;
+    let body = body(&attrs, &quote! { Self }, &fields);
+
+    // the keys this struct reads when it is flattened into another one
+    let claims = if fields.iter().any(|f| attrs.extra(f)) {
+        quote! { true }
+    } else {
+        let claimed_types = fields.iter().filter(|f| attrs.flatten(f)).map(|f| &f.ty);
+        let claimed_keys = fields
+            .iter()
+            .filter(|f| !attrs.flatten(f))
+            .flat_map(|f| {
+                std::iter::once(attrs.deserialize_name_field(f)).chain(attrs.field_aliases(f))
+            })
+            .collect::<Vec<_>>();
+        let own = if claimed_keys.is_empty() {
+            quote! { false }
+        } else {
+            quote! { matches!(__deser_key, #(#claimed_keys)|*) }
+        };
+        quote! {
+            #own #(|| <#claimed_types as #krate::Deserialize<'_>>::__claims_key(__deser_key))*
+        }
+    };
+
+    let expanded = quote! {
+        impl #impl_generics #deserialize_trait <#derive_lt> for #self_ty #ty_generics #where_clause {
+            #[inline]
+            #[allow(clippy::forget_copy)]
+            #[allow(clippy::forget_non_drop)]
+            fn from_tape(__deser_tape: &mut #krate::Tape <#derive_lt>) -> #krate::de::Result<Self>
+            where
+                Self: std::marker::Sized + #derive_lt
+            {
+                #body
+            }
+
+            #[inline]
+            fn __claims_key(__deser_key: &str) -> bool {
+                #claims
+            }
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+/// The function a field is read with, `deserialize_with` if set
+pub(crate) fn read_fn(attrs: &StructAttrs, field: &Field) -> TokenStream2 {
+    let krate = attrs.crate_path();
+    attrs.deserialize_with(field).map_or_else(
+        || quote! { #krate::Deserialize::from_tape },
+        |f| quote! { #f },
+    )
+}
+
+/// Reads an object from `__deser_tape` and builds `constructor { .. }` from it,
+/// this is shared between named structs and named enum variants.
+pub(crate) fn body<'field>(
+    attrs: &StructAttrs,
+    constructor: &TokenStream2,
+    fields: impl IntoIterator<Item = &'field Field>,
+) -> TokenStream2 {
+    let krate = attrs.crate_path();
     let mut value_keys = Vec::new();
+    let mut value_patterns = Vec::new();
     let mut value_locals = Vec::new();
     let mut values = Vec::new();
 
     let mut default_keys = Vec::new();
+    let mut default_patterns = Vec::new();
     let mut default_locals = Vec::new();
     let mut defaults = Vec::new();
-    
+    let mut default_values = Vec::new();
+
     let mut options = Vec::new();
     let mut option_locals = Vec::new();
     let mut option_keys = Vec::new();
+    let mut option_patterns = Vec::new();
 
-    let deny_unknown_fields: bool = attrs.deny_unknown_fields();
-    let params = &generics.params;
-    let (all_generics, derive_lt) = match params.first() {
-        None => (quote! { <'input> }, quote! { 'input }),
-        Some(GenericParam::Lifetime(lifetime)) => (quote! { <#params> }, quote! { #lifetime }),
-        Some(_) => (quote! { <'input, #params> }, quote! { 'input }),
-    };
-    for (id, f) in fields.iter().enumerate() {
-        let mut is_option = false;
-        if let Type::Path(TypePath {
-            path: Path { segments, .. },
-            ..
-        }) = &f.ty
-        {
-            if let Some(PathSegment { ident, .. }) = segments.first() {
-                is_option = ident == "Option";
-            }
-        }
+    let mut flattens = Vec::new();
+    let mut flatten_types = Vec::new();
+    let mut flatten_locals = Vec::new();
+    let mut flatten_lens = Vec::new();
+    let mut extras = Vec::new();
+    let mut extra_locals = Vec::new();
+    let mut extra_lens = Vec::new();
+
+    let mut skipped = Vec::new();
+    let mut skipped_values = Vec::new();
+
+    // the function each value, default and option is read with
+    let mut value_reads = Vec::new();
+    let mut default_reads = Vec::new();
+    let mut option_reads = Vec::new();
 
+    let deny_unknown_fields: bool = attrs.deny_unknown_fields();
+    for (id, f) in fields.into_iter().enumerate() {
         let ident = f.ident.clone().expect("Missing ident");
-        let name = attrs.name_field(f);
-        let name = name.trim_matches(':').trim_matches('"').to_string();
-        if is_option {
+        let name = attrs.deserialize_name_field(f);
+        let aliases = attrs.field_aliases(f);
+        let pattern = quote! { #name #(| #aliases)* };
+        let fallback = attrs.default_fallback(f, &Member::Named(ident.clone()));
+        let read = read_fn(attrs, f);
+        // for `Option<Option<T>>` the inner option is read so `null` is kept apart from a missing key
+        let optional = attrs.optional(f);
+        let read_value = match optional {
+            Some(Optional::Double(inner)) => quote! {
+                Some(<#inner as #krate::Deserialize<'_>>::from_tape(__deser_tape)?)
+            },
+            _ => quote! { #read(__deser_tape)? },
+        };
+        if attrs.skip_deserializing(f) {
+            skipped.push(ident);
+            skipped_values
+                .push(fallback.unwrap_or_else(|| quote! { ::std::default::Default::default() }));
+        } else if attrs.extra(f) {
+            extras.push(ident);
+            extra_locals.push(format_ident!("__extra_{}", id));
+            extra_lens.push(format_ident!("__extra_len_{}", id));
+        } else if attrs.flatten(f) {
+            flattens.push(ident);
+            flatten_types.push(&f.ty);
+            flatten_locals.push(format_ident!("__flatten_{}", id));
+            flatten_lens.push(format_ident!("__flatten_len_{}", id));
+        } else if let Some(fallback) = fallback {
+            default_keys.push(name);
+            default_patterns.push(pattern);
+            default_locals.push(format_ident!("__default_{}", id));
+            defaults.push(ident);
+            default_values.push(fallback);
+            default_reads.push(read_value);
+        } else if optional.is_some() {
             options.push(ident);
             option_locals.push(format_ident!("__option_{}", id));
             option_keys.push(name);
+            option_patterns.push(pattern);
+            option_reads.push(read_value);
         } else {
-            if attrs.default_fallback(f) {
-                default_keys.push(name);
-                default_locals.push(format_ident!("__default_{}", id));
-                defaults.push(ident);
-            }else {
-                values.push(ident);
-                value_locals.push(format_ident!("__value_{}", id));
-                value_keys.push(name);
-            }
+            values.push(ident);
+            value_locals.push(format_ident!("__value_{}", id));
+            value_keys.push(name);
+            value_patterns.push(pattern);
+            value_reads.push(read);
         }
     }
 
-    let expanded = quote! {
-        impl #all_generics ::simd_json_derive::Deserialize <#derive_lt> for #ident #generics {
-            #[inline]
-            #[allow(clippy::forget_copy)]
-            #[allow(clippy::forget_non_drop)]
-            fn from_tape(__deser_tape: &mut ::simd_json_derive::Tape <#derive_lt>) -> ::simd_json_derive::de::Result<Self>
-            where
-                Self: std::marker::Sized + #derive_lt
-            {
-                let __deser_len: usize = if let Some(::simd_json::Node::Object{len, ..}) = __deser_tape.next() {
-                    len
-                } else {
-                    return Err(::simd_json_derive::de::Error::InvalidStructRepresentation);
-                };
-
-                #(let mut #value_locals = None;)*
-                #(let mut #default_locals = None;)*
-                #(let mut #option_locals = None;)*
-
-                for _ in 0..__deser_len {
-                    match __deser_tape.next() {
-                        Some(::simd_json::Node::String(__deser_key)) =>  {
-                            match __deser_key {
-                                #(
-                                #value_keys => {
-                                    let v = ::simd_json_derive::Deserialize::from_tape(__deser_tape)?;
-                                    #value_locals = Some(v);
-                                }
-                                )*
-                                #(
-                                #default_keys => {
-                                    let v = ::simd_json_derive::Deserialize::from_tape(__deser_tape)?;
-                                    #default_locals = Some(v);
-                                }
-                                )*
-                                #(
-                                #option_keys => {
-                                    #option_locals = ::simd_json_derive::Deserialize::from_tape(__deser_tape)?;
-                                }
-                                )*
-                                __unknown_field if #deny_unknown_fields => {
-                                    return Err(::simd_json_derive::de::Error::UnknownField(__unknown_field.to_string(), &[ #(#value_keys,)* #(#option_keys,)* ]));
-                                }
-                                _ => {
-                                    // ignore unknown field
-                                    ::simd_json_derive::__skip(1, __deser_tape)
-                                }
-                            }
-                        },
-                        // There are no more elements
-                        _ => break
+    // keys not claimed by any field are either skipped or, if there are flattened
+    // fields, collected into an object per flattened field that claims them, keys
+    // none of them claims go to the `extra` fields or, if there are none, to all
+    // flattened fields so a flattened map gets only the keys no flattened struct reads
+    let (unclaimed_locals, unclaimed_lens) = if extras.is_empty() {
+        (&flatten_locals, &flatten_lens)
+    } else {
+        (&extra_locals, &extra_lens)
+    };
+    let (flatten_init, unknown) = if flattens.is_empty() && extras.is_empty() {
+        (
+            quote! {},
+            quote! {
+                __unknown_field if #deny_unknown_fields => {
+                    return Err(#krate::de::Error::UnknownField(__unknown_field.to_string(), &[ #(#value_keys,)* #(#default_keys,)* #(#option_keys,)* ]));
+                }
+                _ => {
+                    // ignore unknown field
+                    #krate::__skip(1, __deser_tape)
+                }
+            },
+        )
+    } else {
+        (
+            quote! {
+                #(
+                    let mut #flatten_locals = Vec::new();
+                    let mut #flatten_lens: usize = 0;
+                )*
+                #(
+                    let mut #extra_locals = Vec::new();
+                    let mut #extra_lens: usize = 0;
+                )*
+            },
+            quote! {
+                _ => {
+                    let __deser_value = #krate::__subtree(__deser_tape);
+                    let mut __deser_claimed = false;
+                    #(
+                        if <#flatten_types as #krate::Deserialize<'_>>::__claims_key(__deser_key) {
+                            __deser_claimed = true;
+                            #flatten_locals.push(#krate::__private::Node::String(__deser_key));
+                            #flatten_locals.extend_from_slice(&__deser_value);
+                            #flatten_lens += 1;
+                        }
+                    )*
+                    if !__deser_claimed {
+                        #(
+                            #unclaimed_locals.push(#krate::__private::Node::String(__deser_key));
+                            #unclaimed_locals.extend_from_slice(&__deser_value);
+                            #unclaimed_lens += 1;
+                        )*
                     }
                 }
-                Ok(#ident {
+            },
+        )
+    };
+
+    let container_default = attrs
+        .container_default()
+        .map(|default| quote! { let __deser_default: Self = #default; });
+
+    quote! {
+        let __deser_len: usize = if let Some(#krate::__private::Node::Object{len, ..}) = __deser_tape.next() {
+            len
+        } else {
+            return Err(#krate::de::Error::InvalidStructRepresentation);
+        };
+
+        #(let mut #value_locals = None;)*
+        #(let mut #default_locals = None;)*
+        #(let mut #option_locals = None;)*
+        #flatten_init
+
+        for _ in 0..__deser_len {
+            match __deser_tape.next() {
+                Some(#krate::__private::Node::String(__deser_key)) =>  {
+                    match __deser_key {
                         #(
-                            #options: #option_locals,
+                        #value_patterns => {
+                            let v = #value_reads(__deser_tape)?;
+                            #value_locals = Some(v);
+                        }
                         )*
                         #(
-                            #defaults: #default_locals.unwrap_or_else(|| Default::default()),
+                        #default_patterns => {
+                            let v = #default_reads;
+                            #default_locals = Some(v);
+                        }
                         )*
                         #(
-                            #values: #value_locals.ok_or_else(|| ::simd_json_derive::de::Error::MissingField(#value_keys))?,
+                        #option_patterns => {
+                            #option_locals = #option_reads;
+                        }
                         )*
-                })
+                        #unknown
+                    }
+                },
+                // There are no more elements
+                _ => break
             }
         }
-    };
-    TokenStream::from(expanded)
+        #container_default
+        Ok(#constructor {
+                #(
+                    #options: #option_locals,
+                )*
+                #(
+                    #defaults: match #default_locals {
+                        Some(v) => v,
+                        None => #default_values,
+                    },
+                )*
+                #(
+                    #values: #value_locals.ok_or_else(|| #krate::de::Error::MissingField(#value_keys))?,
+                )*
+                #(
+                    #flattens: #krate::__flattened(#flatten_lens, &#flatten_locals)?,
+                )*
+                #(
+                    #extras: #krate::__flattened(#extra_lens, &#extra_locals)?,
+                )*
+                #(
+                    #skipped: #skipped_values,
+                )*
+        })
+    }
 }
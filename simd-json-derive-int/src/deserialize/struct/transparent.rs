@@ -0,0 +1,66 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Field, Fields, Ident, Member};
+
+use crate::{args::StructAttrs, bound::DeserializeGenerics};
+
+/// Transparent struct, read exactly as its only field `member`, all other
+/// fields are set to their field or container default
+pub(crate) fn derive(
+    attrs: &StructAttrs,
+    ident: Ident,
+    generics: DeserializeGenerics,
+    fields: &Fields,
+    member: Member,
+    field: &Field,
+) -> TokenStream {
+    let krate = attrs.crate_path();
+    let deserialize_trait = attrs.deserialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let DeserializeGenerics {
+        impl_generics,
+        ty_generics,
+        where_clause,
+        lifetime: derive_lt,
+    } = generics;
+
+    let read = super::named::read_fn(attrs, field);
+    let (others, other_values): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .zip(fields.members())
+        .filter(|(_, m)| *m != member)
+        .map(|(f, m)| {
+            let value = attrs
+                .default_fallback(f, &m)
+                .unwrap_or_else(|| quote! { ::std::default::Default::default() });
+            (m, value)
+        })
+        .unzip();
+    let container_default = attrs
+        .container_default()
+        .map(|default| quote! { let __deser_default: Self = #default; });
+    let ty = &field.ty;
+    let expanded = quote! {
+        impl #impl_generics #deserialize_trait <#derive_lt> for #self_ty #ty_generics #where_clause {
+            #[inline]
+            fn from_tape(__deser_tape: &mut #krate::Tape<#derive_lt>) -> #krate::de::Result<Self>
+            where
+                Self: std::marker::Sized + #derive_lt
+            {
+                #container_default
+                Ok(Self {
+                    #member: #read(__deser_tape)?,
+                    #(
+                        #others: #other_values,
+                    )*
+                })
+            }
+
+            #[inline]
+            fn __claims_key(__deser_key: &str) -> bool {
+                <#ty as #krate::Deserialize<'_>>::__claims_key(__deser_key)
+            }
+        }
+    };
+    TokenStream::from(expanded)
+}
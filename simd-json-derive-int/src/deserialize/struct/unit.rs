@@ -0,0 +1,47 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use crate::{args::StructAttrs, bound::DeserializeGenerics};
+
+/// Unit struct as `Struct`
+pub(crate) fn derive(
+    attrs: StructAttrs,
+    ident: Ident,
+    generics: DeserializeGenerics,
+) -> TokenStream {
+    let krate = attrs.crate_path();
+    let deserialize_trait = attrs.deserialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let DeserializeGenerics {
+        impl_generics,
+        ty_generics,
+        where_clause,
+        lifetime: derive_lt,
+    } = generics;
+
+    let body = if attrs.unit_as_object() {
+        // read like a named struct without any fields
+        super::named::body(&attrs, &quote! { Self }, &[])
+    } else {
+        quote! {
+            if let Some(#krate::__private::Node::Static(#krate::__private::StaticNode::Null)) = __deser_tape.next() {
+                Ok(Self)
+            } else {
+                Err(#krate::de::Error::expected_null())
+            }
+        }
+    };
+    let expanded = quote! {
+        impl #impl_generics #deserialize_trait <#derive_lt> for #self_ty #ty_generics #where_clause {
+            #[inline]
+            fn from_tape(__deser_tape: &mut #krate::Tape<#derive_lt>) -> #krate::de::Result<Self>
+            where
+                Self: std::marker::Sized + #derive_lt
+            {
+                #body
+            }
+        }
+    };
+    TokenStream::from(expanded)
+}
@@ -1,38 +1,65 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{punctuated::Punctuated, token::Comma, Field, GenericParam, Generics, Ident};
+use syn::{punctuated::Punctuated, token::Comma, Field, Ident};
 
-use crate::args::StructAttrs;
+use crate::{args::StructAttrs, bound::DeserializeGenerics};
 
 pub(crate) fn derive(
-    _attrs: StructAttrs,
+    attrs: StructAttrs,
     ident: Ident,
-    generics: Generics,
+    generics: DeserializeGenerics,
     fields: Punctuated<Field, Comma>,
 ) -> proc_macro::TokenStream {
-    let params = &generics.params;
-    let (all_generics, derive_lt) = match params.first() {
-        None => (quote! { <'input> }, quote! { 'input }),
-        Some(GenericParam::Lifetime(lifetime)) => (quote! { <#params> }, quote! { #lifetime }),
-        Some(_) => (quote! { <'input, #params> }, quote! { 'input }),
-    };
+    let krate = attrs.crate_path();
+    let deserialize_trait = attrs.deserialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let DeserializeGenerics {
+        impl_generics,
+        ty_generics,
+        where_clause,
+        lifetime: derive_lt,
+    } = generics;
 
     if fields.len() == 1 {
         // This is a newtype
+        let read = super::named::read_fn(&attrs, &fields[0]);
 
         let expanded = quote! {
-            impl #all_generics ::simd_json_derive::Deserialize <#derive_lt> for #ident #generics {
+            impl #impl_generics #deserialize_trait <#derive_lt> for #self_ty #ty_generics #where_clause {
                 #[inline]
-                fn from_tape(__deser_tape: &mut ::simd_json_derive::Tape<#derive_lt>) -> ::simd_json_derive::de::Result<Self>
+                fn from_tape(__deser_tape: &mut #krate::Tape<#derive_lt>) -> #krate::de::Result<Self>
                 where
                     Self: std::marker::Sized + #derive_lt
                 {
-                    ::simd_json_derive::Deserialize::from_tape(__deser_tape).map(Self)
+                    #read(__deser_tape).map(Self)
                 }
             }
         };
         TokenStream::from(expanded)
     } else {
-        todo!("Only newtype unnamed structs are supported by now")
+        let len = fields.len();
+        let fields = fields.iter().map(|f| {
+            let read = super::named::read_fn(&attrs, f);
+            quote! { #read(__deser_tape)? }
+        });
+        let expanded = quote! {
+            impl #impl_generics #deserialize_trait <#derive_lt> for #self_ty #ty_generics #where_clause {
+                #[inline]
+                fn from_tape(__deser_tape: &mut #krate::Tape<#derive_lt>) -> #krate::de::Result<Self>
+                where
+                    Self: std::marker::Sized + #derive_lt
+                {
+                    match __deser_tape.next() {
+                        Some(#krate::__private::Node::Array{len: #len, ..}) => Ok(Self(#(#fields),*)),
+                        Some(#krate::__private::Node::Array{len, ..}) => Err(#krate::de::Error::InvalidLength {
+                            expected: #len,
+                            got: len,
+                        }),
+                        _ => Err(#krate::de::Error::expected_array()),
+                    }
+                }
+            }
+        };
+        TokenStream::from(expanded)
     }
 }
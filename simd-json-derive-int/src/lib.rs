@@ -1,7 +1,9 @@
 use proc_macro::TokenStream;
 
 mod args;
+mod bound;
 mod deserialize;
+mod remote;
 mod serialize;
 
 #[proc_macro_derive(Serialize, attributes(serde, simd_json))]
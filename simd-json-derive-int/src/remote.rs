@@ -0,0 +1,132 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, Fields, Generics, Ident, Member, Path};
+
+use crate::bound::DeserializeGenerics;
+
+/// Wraps the `Serialize` impl derived for the `remote` type into a `json_write` function
+/// of `ident`, to be used with `with = "Ident"`. The impl can't be for the real trait as
+/// neither it nor the remote type are local, so it is for a local trait with the same
+/// functions, see `StructAttrs::serialize_trait`.
+pub(crate) fn serialize(
+    remote: &Path,
+    ident: &Ident,
+    data: &Data,
+    generics: &Generics,
+    derived: TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let derived = TokenStream2::from(derived);
+    let used = pretend_used(ident, data);
+    let expanded = quote! {
+        const _: () = {
+            trait __RemoteSerialize {
+                fn json_write<W>(&self, writer: &mut W) -> std::io::Result<()>
+                where
+                    W: std::io::Write;
+
+                // only declared so the derived impls of structs need no changes, never called
+                #[allow(dead_code)]
+                fn __json_write_fields<W>(&self, _writer: &mut W, has_written_key: bool) -> std::io::Result<bool>
+                where
+                    W: std::io::Write {
+                        Ok(has_written_key)
+                    }
+            }
+
+            #derived
+
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #[inline]
+                pub fn json_write<W>(value: &#remote #ty_generics, writer: &mut W) -> std::io::Result<()>
+                where
+                    W: std::io::Write {
+                        #used
+                        __RemoteSerialize::json_write(value, writer)
+                    }
+            }
+        };
+    };
+    TokenStream::from(expanded)
+}
+
+/// Wraps the `Deserialize` impl derived for the `remote` type into a `from_tape` function
+/// of `ident`, to be used with `with = "Ident"`
+pub(crate) fn deserialize(
+    krate: &Path,
+    remote: &Path,
+    ident: &Ident,
+    data: &Data,
+    generics: &DeserializeGenerics,
+    derived: TokenStream,
+) -> TokenStream {
+    let DeserializeGenerics {
+        impl_generics,
+        ty_generics,
+        where_clause,
+        lifetime: derive_lt,
+    } = generics;
+    let derived = TokenStream2::from(derived);
+    let used = pretend_used(ident, data);
+    let expanded = quote! {
+        const _: () = {
+            trait __RemoteDeserialize<'input> {
+                fn from_tape(__deser_tape: &mut #krate::Tape<'input>) -> #krate::de::Result<Self>
+                where
+                    Self: std::marker::Sized + 'input;
+
+                // see `__RemoteSerialize::__json_write_fields`
+                #[allow(dead_code)]
+                fn __claims_key(_key: &str) -> bool {
+                    false
+                }
+            }
+
+            #derived
+
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #[inline]
+                pub fn from_tape(__deser_tape: &mut #krate::Tape<#derive_lt>) -> #krate::de::Result<#remote #ty_generics>
+                where
+                    #remote #ty_generics: #derive_lt
+                {
+                    #used
+                    <#remote #ty_generics as __RemoteDeserialize<#derive_lt>>::from_tape(__deser_tape)
+                }
+            }
+        };
+    };
+    TokenStream::from(expanded)
+}
+
+/// Code that builds and takes apart `ident` without ever running, the type only
+/// mirrors the remote one so otherwise it and its fields would be reported as unused
+fn pretend_used(ident: &Ident, data: &Data) -> TokenStream2 {
+    let shapes: Vec<(TokenStream2, &Fields)> = match data {
+        Data::Struct(data) => vec![(quote! { #ident }, &data.fields)],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|v| {
+                let variant = &v.ident;
+                (quote! { #ident::#variant }, &v.fields)
+            })
+            .collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    let uses = shapes.iter().map(|(path, fields)| {
+        let members: Vec<Member> = fields.members().collect();
+        let bindings = (0..members.len()).map(|i| format_ident!("__used_{}", i));
+        quote! {
+            let __used = #path { #(#members: ::std::unreachable!(),)* };
+            if let #path { #(#members: #bindings,)* } = __used {}
+        }
+    });
+    quote! {
+        #[allow(unreachable_code, unused_variables, irrefutable_let_patterns, clippy::diverging_sub_expression)]
+        if false {
+            #(#uses)*
+        }
+    }
+}
@@ -1,30 +1,45 @@
 use proc_macro::{self, TokenStream};
-use quote::quote;
 use syn::{Data, DeriveInput};
 
-use crate::args::StructAttrs;
+use crate::{args::StructAttrs, bound, remote};
 
 mod r#enum;
+mod into;
+mod repr;
 mod r#struct;
 
 pub(crate) fn derive(input: TokenStream) -> TokenStream {
-    let input = syn::parse_macro_input!(input as DeriveInput);
-    match input {
-        // struct
-        DeriveInput {
-            ident,
-            attrs,
-            data: Data::Struct(defn),
-            generics,
-            ..
-        } => r#struct::derive(StructAttrs::parse(attrs), ident, generics, defn),
-        DeriveInput {
-            ident,
-            data: Data::Enum(data),
-            generics,
-            attrs,
-            ..
-        } => r#enum::derive(StructAttrs::parse(attrs), ident, data, generics),
-        _ => TokenStream::from(quote! {}),
+    let DeriveInput {
+        ident,
+        attrs,
+        data,
+        generics,
+        ..
+    } = syn::parse_macro_input!(input as DeriveInput);
+    let attrs = match StructAttrs::parse(attrs, &data) {
+        Ok(attrs) => attrs,
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
+    let generics = bound::serialize(&attrs, &data, &generics);
+    let remote = attrs.remote().map(|remote| (remote.clone(), data.clone()));
+    let derived = if let Some(into) = attrs.converted_into() {
+        into::derive(&attrs, into, ident.clone(), generics.clone())
+    } else {
+        match data {
+            // struct
+            Data::Struct(defn) => r#struct::derive(attrs, ident.clone(), generics.clone(), defn),
+            Data::Enum(data) => match attrs.repr() {
+                Some(repr) => repr::derive(&attrs, repr, ident.clone(), data, generics.clone()),
+                None => r#enum::derive(attrs, ident.clone(), data, generics.clone()),
+            },
+            Data::Union(data) => TokenStream::from(
+                syn::Error::new_spanned(data.union_token, "unions are not supported")
+                    .to_compile_error(),
+            ),
+        }
+    };
+    match remote {
+        Some((remote, data)) => remote::serialize(&remote, &ident, &data, &generics, derived),
+        None => derived,
     }
 }
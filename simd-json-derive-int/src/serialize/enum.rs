@@ -4,7 +4,7 @@ use quote::quote;
 use simd_json::prelude::Writable as _;
 use syn::{DataEnum, Fields, Generics, Variant};
 
-use crate::args::StructAttrs;
+use crate::args::{StructAttrs, Tagging};
 
 pub(crate) fn derive(
     attrs: StructAttrs,
@@ -12,10 +12,23 @@ pub(crate) fn derive(
     data: DataEnum,
     generics: Generics,
 ) -> TokenStream {
+    let krate = attrs.crate_path();
+    let serialize_trait = attrs.serialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let mut body_elements = Vec::new();
+    // an `other` variant with a field is written as the name it was read with
+    let other = match attrs.other_variant(&data) {
+        Ok(other) => other
+            .filter(|v| !v.fields.is_empty())
+            .map(|v| v.ident.clone()),
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
     let variants = data.variants;
-    let (simple, variants): (Vec<_>, Vec<_>) =
-        variants.into_iter().partition(|v| v.fields.is_empty());
+    let (simple, variants): (Vec<_>, Vec<_>) = variants
+        .into_iter()
+        .filter(|v| Some(&v.ident) != other.as_ref())
+        .partition(|v| v.fields.is_empty());
     let (named, unnamed): (Vec<_>, Vec<_>) = variants.iter().partition(|v| {
         matches!(
             v,
@@ -29,21 +42,73 @@ pub(crate) fn derive(
     let (unnamed1, unnamed): (Vec<_>, Vec<_>) =
         unnamed.into_iter().partition(|v| v.fields.len() == 1);
 
+    let tagging = attrs.tagging();
+    let internal = matches!(tagging, Tagging::Internal { .. });
+    if let (true, Some(v)) = (internal, unnamed.first()) {
+        return TokenStream::from(
+            syn::Error::new_spanned(v, "internally tagged enums do not support tuple variants")
+                .to_compile_error(),
+        );
+    }
+    // the payload of a newtype variant is written inline, which a `serialize_with` function can't do
+    if let (true, Some(f)) = (
+        internal,
+        unnamed1
+            .iter()
+            .flat_map(|v| &v.fields)
+            .find(|f| attrs.serialize_with(f).is_some()),
+    ) {
+        return TokenStream::from(
+            syn::Error::new_spanned(
+                f,
+                "internally tagged enums do not support `with` on newtype variants",
+            )
+            .to_compile_error(),
+        );
+    }
+
+    // The start of a variant that carries a payload, for internally tagged enums
+    // the payload is written inline, otherwise it is followed by the payload and `close`
+    let open = |name: &str| match tagging {
+        Tagging::External => format!("{{{name}:"),
+        Tagging::Untagged => String::new(),
+        Tagging::Internal { tag } => {
+            format!("{{{}:{name}", simd_json::OwnedValue::from(tag).encode())
+        }
+        Tagging::Adjacent { tag, content } => format!(
+            "{{{}:{name},{}:",
+            simd_json::OwnedValue::from(tag).encode(),
+            simd_json::OwnedValue::from(content).encode()
+        ),
+    };
+    let close = if let Tagging::Untagged = tagging {
+        ""
+    } else {
+        "}"
+    };
+
     // enum no fields of Enum::Variant
     // They serialize as: "Variant"
+    // or, tagged, as: {"tag":"Variant"}
+    // or, untagged, as: null
 
     let (simple_keys, simple_values): (Vec<_>, Vec<_>) = simple
         .iter()
         .map(|s| {
-            (
-                &s.ident,
-                simd_json::OwnedValue::from(attrs.name_variant(&s)).encode(),
-            )
+            let name = simd_json::OwnedValue::from(attrs.name_variant(s)).encode();
+            match tagging {
+                Tagging::External => (&s.ident, name),
+                Tagging::Untagged => (&s.ident, String::from("null")),
+                Tagging::Internal { tag } | Tagging::Adjacent { tag, .. } => (
+                    &s.ident,
+                    format!("{{{}:{name}}}", simd_json::OwnedValue::from(tag).encode()),
+                ),
+            }
         })
         .unzip();
     let simple = quote! {
         #(
-            #ident::#simple_keys => writer.write_all(#simple_values.as_bytes())
+            Self::#simple_keys => writer.write_all(#simple_values.as_bytes())
         ),*
     };
 
@@ -51,29 +116,75 @@ pub(crate) fn derive(
         body_elements.push(simple);
     }
 
+    if let Some(other) = other {
+        let write = match tagging {
+            Tagging::External | Tagging::Untagged => quote! {
+                #krate::Serialize::json_write(__other, writer)
+            },
+            Tagging::Internal { tag } | Tagging::Adjacent { tag, .. } => {
+                let prefix = format!("{{{}:", simd_json::OwnedValue::from(tag).encode());
+                quote! {
+                    writer.write_all(#prefix.as_bytes())?;
+                    #krate::Serialize::json_write(__other, writer)?;
+                    writer.write_all(b"}")
+                }
+            }
+        };
+        body_elements.push(quote! {
+            Self::#other(__other) => {
+                #write
+            }
+        });
+    }
+
     // Unnamed enum variants with exactly 1 field of Enum::Variant(type1)
     // They serialize as: {"Varriant":..}
+    // or, internally tagged, as: {"tag":"Variant",..fields of type1}
+    // or, adjacently tagged, as: {"tag":"Variant","content":..}
+    // or, untagged, as: ..
 
     let (unnamed1_idents, unnamed1_keys): (Vec<_>, Vec<_>) = unnamed1
         .iter()
         .map(|v| {
             (
                 &v.ident,
-                format!(
-                    "{{{}:",
-                    simd_json::OwnedValue::from(attrs.name_variant(&v)).encode()
-                ),
+                open(&simd_json::OwnedValue::from(attrs.name_variant(v)).encode()),
             )
         })
         .unzip();
-    let unnamed1 = quote! {
-        #(
-            #ident::#unnamed1_idents(v) => {
-                writer.write_all(#unnamed1_keys.as_bytes())?;
-                v.json_write(writer)?;
-                writer.write_all(b"}")
-            }
-        ),*
+    let unnamed1_writes = unnamed1
+        .iter()
+        .flat_map(|v| &v.fields)
+        .map(|f| super::r#struct::named::write_value(&attrs, f, quote! { v }));
+    let unnamed1 = if let Tagging::Untagged = tagging {
+        quote! {
+            #(
+                Self::#unnamed1_idents(v) => {
+                    #unnamed1_writes;
+                    Ok(())
+                }
+            ),*
+        }
+    } else if internal {
+        quote! {
+            #(
+                Self::#unnamed1_idents(v) => {
+                    writer.write_all(#unnamed1_keys.as_bytes())?;
+                    #krate::Serialize::__json_write_fields(v, writer, true)?;
+                    writer.write_all(b"}")
+                }
+            ),*
+        }
+    } else {
+        quote! {
+            #(
+                Self::#unnamed1_idents(v) => {
+                    writer.write_all(#unnamed1_keys.as_bytes())?;
+                    #unnamed1_writes;
+                    writer.write_all(b"}")
+                }
+            ),*
+        }
     };
     if !unnamed1.is_empty() {
         body_elements.push(unnamed1);
@@ -81,6 +192,8 @@ pub(crate) fn derive(
 
     // Unnamed enum variants with more then 1 field of Enum::Variant(type1, type2, type3)
     // They serialize as: {"Varriant":[.., .., ..]}
+    // or, adjacently tagged, as: {"tag":"Variant","content":[.., .., ..]}
+    // or, untagged, as: [.., .., ..]
 
     let (unnamed_ident_and_vars, unnamed_keys): (Vec<_>, Vec<_>) = unnamed
         .iter()
@@ -93,8 +206,8 @@ pub(crate) fn derive(
                         .collect::<Vec<_>>(),
                 ),
                 format!(
-                    "{{{}:[",
-                    simd_json::OwnedValue::from(attrs.name_variant(&v)).encode()
+                    "{}[",
+                    open(&simd_json::OwnedValue::from(attrs.name_variant(v)).encode())
                 ),
             )
         })
@@ -103,26 +216,32 @@ pub(crate) fn derive(
     let (unnamed_idents, unnamed_var_names): (Vec<_>, Vec<_>) =
         unnamed_ident_and_vars.into_iter().unzip();
 
-    let unnamed_vecs = unnamed_var_names.iter().map(|vs| {
-        let (first, rest) = vs.split_first().expect("zero unnamed vars");
+    let unnamed_vecs = unnamed.iter().zip(&unnamed_var_names).map(|(v, vs)| {
+        let mut writes = v
+            .fields
+            .iter()
+            .zip(vs)
+            .map(|(f, var)| super::r#struct::named::write_value(&attrs, f, quote! { #var }));
+        let first = writes.next().expect("zero unnamed vars");
         quote! {
-            #first.json_write(writer)?;
+            #first;
             #(
                 writer.write_all(b",")?;
-                #rest.json_write(writer)?;
+                #writes;
             )*
         }
     });
 
     let unnamed_vars = unnamed_var_names.iter().map(|vs| quote! { #(#vs),* });
+    let unnamed_close = format!("]{close}");
 
     let unnamed = quote! {
         #(
-            #ident::#unnamed_idents(#unnamed_vars) =>
+            Self::#unnamed_idents(#unnamed_vars) =>
             {
                 writer.write_all(#unnamed_keys.as_bytes())?;
                 #unnamed_vecs
-                writer.write_all(b"]}")
+                writer.write_all(#unnamed_close.as_bytes())
             }
         ),*
     };
@@ -132,87 +251,79 @@ pub(crate) fn derive(
 
     // Named enum variants of the form Enum::Variant{key1: type, key2: type...}
     // They serialize as: {"Varriant":{"key1":..,"key2":..}}
+    // or, internally tagged, as: {"tag":"Variant","key1":..,"key2":..}
+    // or, adjacently tagged, as: {"tag":"Variant","content":{"key1":..,"key2":..}}
+    // or, untagged, as: {"key1":..,"key2":..}
 
+    let fields_attrs = attrs.variant_fields();
     let mut named_bodies = Vec::new();
     for v in named {
         let named_ident = &v.ident;
         let mut keys = Vec::new();
         let mut values = Vec::new();
-        let mut skip_if = Vec::new();
+        let mut value_writes = Vec::new();
 
-        for f in &v.fields {
-            let name = attrs.name_field(f);
+        for f in v
+            .fields
+            .iter()
+            .filter(|f| !fields_attrs.skip_serializing(f))
+        {
+            let name = fields_attrs.name_field(f);
             let ident = f.ident.clone().expect("Missing ident");
             keys.push(name);
+            value_writes.push(super::r#struct::named::write_value(
+                &fields_attrs,
+                f,
+                quote! { #ident },
+            ));
             values.push(ident);
-            skip_if.push(attrs.skip_serializing_if(f));
         }
-        let variant_name = simd_json::OwnedValue::from(attrs.name_variant(&v)).encode();
-
-        named_bodies.push(if skip_if.iter().all(Option::is_none) {
-            let (first_key, rest_keys) = keys.split_first().expect("zero fields");
-            let (first_value, rest_values) = values.split_first().expect("zero fields");
-
-            let start = format!("{{{variant_name}:{{{first_key}",);
-            let rest_keys = rest_keys
-                .iter()
-                .map(|k| format!(",{k}"))
-                .collect::<Vec<_>>();
-
-            quote! {
-                #ident::#named_ident{#(#values),*} => {
-                    writer.write_all(#start.as_bytes())?;
-                    #first_value.json_write(writer)?;
-                    #(
-                        writer.write_all(#rest_keys.as_bytes())?;
-                        #rest_values.json_write(writer)?;
-
-                    )*
-                    writer.write_all(b"}}")
-                }
-            }
+        let variant_name = simd_json::OwnedValue::from(attrs.name_variant(v)).encode();
+        let (prefix, suffix, has_written_key) = if internal {
+            (open(&variant_name), String::from("}"), true)
         } else {
-            let writes = keys
-                .iter()
-                .zip(values.iter())
-                .zip(skip_if.iter())
-                .map(|((k, v), s)| {
-                    if let Some(s) = s {
-                        quote! {
-
-                            if !#s(#v) {
-                                if has_written_key {
-                                    writer.write_all(b",")?;
-                                }
-                                has_written_key = true;
-                                writer.write_all(#k.as_bytes())?;
-                                #v.json_write(writer)?;
-                            }
-                        }
+            (
+                format!("{}{{", open(&variant_name)),
+                format!("}}{close}"),
+                false,
+            )
+        };
+
+        named_bodies.push(
+            if super::r#struct::named::has_fixed_keys(&fields_attrs, &v.fields) {
+                let keys = keys.iter().enumerate().map(|(i, k)| {
+                    if i > 0 || has_written_key {
+                        format!(",{k}")
                     } else {
-                        quote! {
-                            if has_written_key {
-                                writer.write_all(b",")?;
-                            }
-                            has_written_key = true;
-                            writer.write_all(#k.as_bytes())?;
-                            #v.json_write(writer)?;
-                        }
+                        k.clone()
+                    }
+                });
+
+                quote! {
+                    Self::#named_ident{#(#values,)* ..} => {
+                        writer.write_all(#prefix.as_bytes())?;
+                        #(
+                            writer.write_all(#keys.as_bytes())?;
+                            #value_writes;
+                        )*
+                        writer.write_all(#suffix.as_bytes())
                     }
-                })
-                .collect::<Vec<_>>();
-            let prefix = format!("{{{variant_name}:{{");
-            quote! {
-                #ident::#named_ident{#(#values),*} => {
-                    writer.write_all(#prefix.as_bytes())?;
-                    let mut has_written_key = false;
-                    #(
-                        #writes
-                    )*
-                    writer.write_all(b"}}")
                 }
-            }
-        });
+            } else {
+                let writes =
+                    super::r#struct::named::writes(&fields_attrs, &v.fields, |_, v| quote! { #v });
+                quote! {
+                    Self::#named_ident{#(#values,)* ..} => {
+                        writer.write_all(#prefix.as_bytes())?;
+                        let mut has_written_key = #has_written_key;
+                        #(
+                            #writes
+                        )*
+                        writer.write_all(#suffix.as_bytes())
+                    }
+                }
+            },
+        );
     }
     let named = quote! {#(#named_bodies),*};
 
@@ -225,7 +336,7 @@ pub(crate) fn derive(
     };
 
     let expanded = quote! {
-        impl #generics simd_json_derive::Serialize for #ident #generics {
+        impl #impl_generics #serialize_trait for #self_ty #ty_generics #where_clause {
             #[inline]
             fn json_write<W>(&self, writer: &mut W) -> std::io::Result<()>
             where
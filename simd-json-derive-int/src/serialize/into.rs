@@ -0,0 +1,30 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident, Type};
+
+use crate::args::StructAttrs;
+
+/// Any type with `into = "Type"`, a clone is converted and written as `Type`
+pub(crate) fn derive(
+    attrs: &StructAttrs,
+    into: &Type,
+    ident: Ident,
+    generics: Generics,
+) -> TokenStream {
+    let krate = attrs.crate_path();
+    let serialize_trait = attrs.serialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let expanded = quote! {
+        impl #impl_generics #serialize_trait for #self_ty #ty_generics #where_clause {
+            #[inline]
+            fn json_write<W>(&self, writer: &mut W) -> std::io::Result<()>
+            where
+                W: std::io::Write {
+                    let v: #into = ::std::convert::Into::into(::std::clone::Clone::clone(self));
+                    #krate::Serialize::json_write(&v, writer)
+                }
+        }
+    };
+    TokenStream::from(expanded)
+}
@@ -0,0 +1,37 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{DataEnum, Generics, Ident, Type};
+
+use crate::args::StructAttrs;
+
+/// Enum with `repr = "int"`, written as the discriminant of its variants
+pub(crate) fn derive(
+    attrs: &StructAttrs,
+    repr: &Type,
+    ident: Ident,
+    data: DataEnum,
+    generics: Generics,
+) -> TokenStream {
+    let krate = attrs.crate_path();
+    let serialize_trait = attrs.serialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let checks = attrs.repr_checks(&ident, &data);
+    let variants = data.variants.iter().map(|v| &v.ident);
+    let expanded = quote! {
+        #checks
+        impl #impl_generics #serialize_trait for #self_ty #ty_generics #where_clause {
+            #[inline]
+            fn json_write<W>(&self, writer: &mut W) -> std::io::Result<()>
+            where
+                W: std::io::Write {
+                    match self {
+                        #(
+                            Self::#variants => #krate::Serialize::json_write(&(Self::#variants as #repr), writer),
+                        )*
+                    }
+                }
+        }
+    };
+    TokenStream::from(expanded)
+}
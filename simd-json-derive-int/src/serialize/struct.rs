@@ -3,6 +3,8 @@ use syn::{DataStruct, Fields, FieldsNamed, FieldsUnnamed, Generics, Ident};
 use crate::args::StructAttrs;
 
 pub(super) mod named;
+mod transparent;
+pub(super) mod unit;
 pub(super) mod unnamed;
 
 pub(crate) fn derive(
@@ -11,6 +13,13 @@ pub(crate) fn derive(
     generics: Generics,
     defn: DataStruct,
 ) -> proc_macro::TokenStream {
+    match attrs.transparent_field(&defn.fields) {
+        Some(Ok((member, field))) => {
+            return transparent::derive(&attrs, ident, generics, member, field)
+        }
+        Some(Err(e)) => return proc_macro::TokenStream::from(e.to_compile_error()),
+        None => (),
+    }
     match defn {
         DataStruct {
             fields: Fields::Unnamed(FieldsUnnamed { unnamed, .. }),
@@ -24,8 +33,6 @@ pub(crate) fn derive(
         DataStruct {
             fields: Fields::Unit,
             ..
-        } => {
-            todo!("Unit structs are not implemented")
-        }
+        } => unit::derive(attrs, ident, generics),
     }
 }
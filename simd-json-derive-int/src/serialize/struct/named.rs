@@ -1,7 +1,7 @@
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{punctuated::Punctuated, token::Comma, Field, Generics};
+use syn::{punctuated::Punctuated, token::Comma, Field, Generics, Member};
 
 use crate::args::StructAttrs;
 
@@ -12,70 +12,67 @@ pub(crate) fn derive(
     generics: Generics,
     fields: Punctuated<Field, Comma>,
 ) -> proc_macro::TokenStream {
+    let serialize_trait = attrs.serialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let mut keys = Vec::new();
     let mut values = Vec::new();
-    let mut skip_if = Vec::new();
 
-    for f in &fields {
+    for f in fields.iter().filter(|f| !attrs.skip_serializing(f)) {
         let ident = f.ident.clone().expect("Missing ident");
         let name = attrs.name_field(f);
         keys.push(name);
-        values.push(ident);
-        skip_if.push(attrs.skip_serializing_if(f));
+        values.push(write_value(
+            &attrs,
+            f,
+            attrs.field_ref(f, &Member::Named(ident)),
+        ));
     }
-    let expanded = if skip_if.iter().all(Option::is_none) {
-        if let Some((first, rest)) = keys.split_first_mut() {
+    let writes = writes(&attrs, &fields, |f, v| {
+        attrs.field_ref(f, &Member::Named(v.clone()))
+    });
+    let write_fields = quote! {
+        #[inline]
+        #[allow(unused_mut)]
+        fn __json_write_fields<W>(&self, writer: &mut W, mut has_written_key: bool) -> std::io::Result<bool>
+        where
+            W: std::io::Write {
+                #(
+                    #writes
+                )*
+                Ok(has_written_key)
+            }
+    };
+    let expanded = if has_fixed_keys(&attrs, &fields) {
+        // with no keys left to write the opening `{` is written together with the closing one
+        let close = if let Some((first, rest)) = keys.split_first_mut() {
             *first = format!("{{{}", first);
             for r in rest {
                 *r = format!(",{}", r);
             }
+            "}"
+        } else {
+            "{}"
         };
 
         quote! {
-            impl #generics simd_json_derive::Serialize for #ident #generics {
+            impl #impl_generics #serialize_trait for #self_ty #ty_generics #where_clause {
                 #[inline]
                 fn json_write<W>(&self, writer: &mut W) -> std::io::Result<()>
                 where
                     W: std::io::Write {
                         #(
                             writer.write_all(#keys.as_bytes())?;
-                            self.#values.json_write(writer)?;
+                            #values;
                         )*
-                        writer.write_all(b"}")
+                        writer.write_all(#close.as_bytes())
                     }
+                #write_fields
             }
         }
     } else {
-        let writes = keys
-            .iter()
-            .zip(values.iter())
-            .zip(skip_if.iter())
-            .map(|((k, v), s)| {
-                if let Some(s) = s {
-                    quote! {
-                        if !#s(&self.#v) {
-                            if has_written_key {
-                                writer.write_all(b",")?;
-                            }
-                            has_written_key = true;
-                            writer.write_all(#k.as_bytes())?;
-                            self.#v.json_write(writer)?;
-                        }
-                    }
-                } else {
-                    quote! {
-                        if has_written_key {
-                            writer.write_all(b",")?;
-                        }
-                        has_written_key = true;
-                        writer.write_all(#k.as_bytes())?;
-                        self.#v.json_write(writer)?;
-                    }
-                }
-            })
-            .collect::<Vec<_>>();
         quote! {
-            impl #generics simd_json_derive::Serialize for #ident #generics {
+            impl #impl_generics #serialize_trait for #self_ty #ty_generics #where_clause {
                 #[inline]
                 fn json_write<W>(&self, writer: &mut W) -> std::io::Result<()>
                 where
@@ -87,8 +84,71 @@ pub(crate) fn derive(
                         )*
                         writer.write_all(b"}")
                     }
+                #write_fields
             }
         }
     };
     TokenStream::from(expanded)
 }
+
+/// If all keys are always written so the `,` between them is known up front
+pub(crate) fn has_fixed_keys<'field>(
+    attrs: &StructAttrs,
+    fields: impl IntoIterator<Item = &'field Field>,
+) -> bool {
+    fields
+        .into_iter()
+        .filter(|f| !attrs.skip_serializing(f))
+        .all(|f| attrs.skip_serializing_if(f).is_none() && !attrs.flatten(f) && !attrs.extra(f))
+}
+
+/// Writes for fields where a `,` is only known to be needed at runtime, `value`
+/// turns a field and its ident into an expression evaluating to a reference to the field value.
+pub(crate) fn writes<'field>(
+    attrs: &StructAttrs,
+    fields: impl IntoIterator<Item = &'field Field>,
+    value: impl Fn(&Field, &Ident) -> TokenStream2,
+) -> Vec<TokenStream2> {
+    let krate = attrs.crate_path();
+    fields
+        .into_iter()
+        .filter(|f| !attrs.skip_serializing(f))
+        .map(|f| {
+            let v = value(f, f.ident.as_ref().expect("Missing ident"));
+            if attrs.flatten(f) || attrs.extra(f) {
+                return quote! {
+                    has_written_key = #krate::Serialize::__json_write_fields(#v, writer, has_written_key)?;
+                };
+            }
+            let k = attrs.name_field(f);
+            let write_value = write_value(attrs, f, v.clone());
+            let write = quote! {
+                if has_written_key {
+                    writer.write_all(b",")?;
+                }
+                has_written_key = true;
+                writer.write_all(#k.as_bytes())?;
+                #write_value;
+            };
+            if let Some(s) = attrs.skip_serializing_if(f) {
+                quote! {
+                    if !#s(#v) {
+                        #write
+                    }
+                }
+            } else {
+                write
+            }
+        })
+        .collect()
+}
+
+/// Writes the field `value` refers to, using `serialize_with` if set
+pub(crate) fn write_value(attrs: &StructAttrs, field: &Field, value: TokenStream2) -> TokenStream2 {
+    let krate = attrs.crate_path();
+    if let Some(f) = attrs.serialize_with(field) {
+        quote! { #f(#value, writer)? }
+    } else {
+        quote! { #krate::Serialize::json_write(#value, writer)? }
+    }
+}
@@ -0,0 +1,40 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Field, Generics, Ident, Member};
+
+use crate::args::StructAttrs;
+
+/// Transparent struct, written exactly as its only field `member`
+pub(crate) fn derive(
+    attrs: &StructAttrs,
+    ident: Ident,
+    generics: Generics,
+    member: Member,
+    field: &Field,
+) -> TokenStream {
+    let krate = attrs.crate_path();
+    let serialize_trait = attrs.serialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let value = attrs.field_ref(field, &member);
+    let write = super::named::write_value(attrs, field, value.clone());
+    let expanded = quote! {
+        impl #impl_generics #serialize_trait for #self_ty #ty_generics #where_clause {
+            #[inline]
+            fn json_write<W>(&self, writer: &mut W) -> std::io::Result<()>
+            where
+                W: std::io::Write {
+                    #write;
+                    Ok(())
+                }
+
+            #[inline]
+            fn __json_write_fields<W>(&self, writer: &mut W, has_written_key: bool) -> std::io::Result<bool>
+            where
+                W: std::io::Write {
+                    #krate::Serialize::__json_write_fields(#value, writer, has_written_key)
+                }
+        }
+    };
+    TokenStream::from(expanded)
+}
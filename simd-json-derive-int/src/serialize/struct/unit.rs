@@ -0,0 +1,25 @@
+use proc_macro::TokenStream;
+use proc_macro2::Ident;
+use quote::quote;
+use syn::Generics;
+
+use crate::args::StructAttrs;
+
+/// Unit struct as `Struct`
+pub(crate) fn derive(attrs: StructAttrs, ident: Ident, generics: Generics) -> TokenStream {
+    let serialize_trait = attrs.serialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let value = if attrs.unit_as_object() { "{}" } else { "null" };
+    let expanded = quote! {
+        impl #impl_generics #serialize_trait for #self_ty #ty_generics #where_clause {
+            #[inline]
+            fn json_write<W>(&self, writer: &mut W) -> std::io::Result<()>
+            where
+                W: std::io::Write {
+                    writer.write_all(#value.as_bytes())
+                }
+        }
+    };
+    TokenStream::from(expanded)
+}
@@ -2,45 +2,48 @@ use proc_macro::TokenStream;
 use proc_macro2::Ident;
 use quote::quote;
 use syn::token::Comma;
-use syn::{punctuated::Punctuated, Field, Generics};
+use syn::{punctuated::Punctuated, Field, Generics, Index, Member};
 
 use crate::args::StructAttrs;
 
 /// Unnamed struct as `Struct(u8)` or `Struct(u8, String)`
 pub(crate) fn derive(
-    _attrs: StructAttrs,
+    attrs: StructAttrs,
     ident: Ident,
     generics: Generics,
     fields: Punctuated<Field, Comma>,
 ) -> proc_macro::TokenStream {
+    let serialize_trait = attrs.serialize_trait();
+    let self_ty = attrs.self_ty(&ident);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut values = fields.iter().enumerate().map(|(i, f)| {
+        let value = attrs.field_ref(f, &Member::Unnamed(Index::from(i)));
+        super::named::write_value(&attrs, f, value)
+    });
+    let first = values.next();
     if fields.len() == 1 {
         let expanded = quote! {
-            impl #generics simd_json_derive::Serialize for #ident #generics {
+            impl #impl_generics #serialize_trait for #self_ty #ty_generics #where_clause {
                 fn json_write<W>(&self, writer: &mut W) -> std::io::Result<()>
                 where
                     W: std::io::Write {
-                        self.0.json_write(writer)
+                        #first;
+                        Ok(())
                     }
             }
         };
         TokenStream::from(expanded)
     } else {
-        let keys: Vec<_> = fields
-            .iter()
-            .enumerate()
-            .map(|(i, _)| syn::Index::from(i))
-            .skip(1)
-            .collect();
         let expanded = quote! {
-            impl #generics simd_json_derive::Serialize for #ident #generics {
+            impl #impl_generics #serialize_trait for #self_ty #ty_generics #where_clause {
                 fn json_write<W>(&self, writer: &mut W) -> std::io::Result<()>
                 where
                     W: std::io::Write {
                         writer.write_all(b"[")?;
-                        self.0.json_write(writer)?;
+                        #first;
                         #(
                             writer.write_all(b",")?;
-                            self.#keys.json_write(writer)?;
+                            #values;
                         )*
                         writer.write_all(b"]")
                     }
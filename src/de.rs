@@ -1,6 +1,6 @@
 use std::num::TryFromIntError;
 
-use simd_json::Buffers;
+use simd_json::{Buffers, Node};
 
 use crate::Tape;
 
@@ -36,6 +36,9 @@ pub enum Error {
     InvalidEnumRepresentation,
     #[error("invalid struct representation, needs to be an object")]
     InvalidStructRepresentation,
+    /// An array didn't have the expected number of elements
+    #[error("invalid length {got}, expected an array of length {expected}")]
+    InvalidLength { expected: usize, got: usize },
     #[error("Unexpected e,nd of input")]
     EOF,
     #[error("Invalid integer number")]
@@ -89,6 +92,34 @@ pub trait Deserialize<'input> {
     where
         Self: Sized + 'input;
 
+    /// If a key is read by this type when it is flattened into an enclosing
+    /// object, keys that aren't claimed by any field end up in `extra` fields.
+    #[doc(hidden)]
+    #[inline]
+    fn __claims_key(_key: &str) -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+
+    /// Reads this type from the `len` keys, and their values, in `nodes` when it is
+    /// flattened into an enclosing object.
+    #[doc(hidden)]
+    #[inline]
+    fn __from_flattened(len: usize, nodes: &[Node<'input>]) -> Result<Self>
+    where
+        Self: Sized + 'input,
+    {
+        let mut tape = Vec::with_capacity(nodes.len() + 1);
+        tape.push(Node::Object {
+            len,
+            count: nodes.len(),
+        });
+        tape.extend_from_slice(nodes);
+        Self::from_tape(&mut tape.into_iter().peekable())
+    }
+
     #[inline]
     fn from_slice(json: &'input mut [u8]) -> Result<Self>
     where
@@ -25,6 +25,19 @@ where
             writer.write_all(b"null")
         }
     }
+
+    /// A flattened `None` writes no keys at all
+    #[inline]
+    fn __json_write_fields<W>(&self, writer: &mut W, has_written_key: bool) -> io::Result<bool>
+    where
+        W: Write,
+    {
+        if let Some(e) = self {
+            e.__json_write_fields(writer, has_written_key)
+        } else {
+            Ok(has_written_key)
+        }
+    }
 }
 
 impl<'input, T> Deserialize<'input> for Option<T>
@@ -43,6 +56,34 @@ where
             Ok(Some(T::from_tape(tape)?))
         }
     }
+
+    #[inline]
+    fn __claims_key(key: &str) -> bool {
+        T::__claims_key(key)
+    }
+
+    /// A flattened `Option` is `None` if none of the keys is read by `T`
+    #[inline]
+    fn __from_flattened(len: usize, nodes: &[simd_json::Node<'input>]) -> de::Result<Self>
+    where
+        Self: Sized + 'input,
+    {
+        let mut rest = nodes;
+        while let [simd_json::Node::String(key), value, ..] = rest {
+            if T::__claims_key(key) {
+                return T::__from_flattened(len, nodes).map(Some);
+            }
+            // skip the key and the value along with everything nested in it
+            let value_len = match value {
+                simd_json::Node::Array { count, .. } | simd_json::Node::Object { count, .. } => {
+                    count + 1
+                }
+                _ => 1,
+            };
+            rest = &rest[1 + value_len..];
+        }
+        Ok(None)
+    }
 }
 
 impl<TOk, TErr> Serialize for std::result::Result<TOk, TErr>
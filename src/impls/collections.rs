@@ -61,6 +61,21 @@ where
 }
 
 vec_like!([T]);
+
+impl<'input, T> Deserialize<'input> for std::borrow::Cow<'_, [T]>
+where
+    T: Deserialize<'input> + Clone,
+{
+    #[inline]
+    fn from_tape(tape: &mut Tape<'input>) -> de::Result<Self>
+    where
+        Self: Sized + 'input,
+    {
+        // the elements are read from the tape so there is nothing to borrow
+        Ok(std::borrow::Cow::Owned(Vec::from_tape(tape)?))
+    }
+}
+
 vec_like!(collections::VecDeque<T>);
 impl<'input, T> Deserialize<'input> for collections::VecDeque<T>
 where
@@ -216,6 +231,23 @@ macro_rules! ser_map_like {
                     writer.write_all(b"{}")
                 }
             }
+
+            #[inline]
+            fn __json_write_fields<W>(&self, writer: &mut W, mut has_written_key: bool) -> io::Result<bool>
+            where
+                W: Write,
+            {
+                for (k, v) in self.iter() {
+                    if has_written_key {
+                        writer.write_all(b",")?;
+                    }
+                    has_written_key = true;
+                    k.json_write(writer)?;
+                    writer.write_all(b":")?;
+                    v.json_write(writer)?;
+                }
+                Ok(has_written_key)
+            }
         }
     };
 }
@@ -23,7 +23,7 @@ impl<'input> Deserialize<'input> for String {
     }
 }
 
-impl<'input> Deserialize<'input> for &'input str {
+impl<'input: 'str, 'str> Deserialize<'input> for &'str str {
     #[inline]
     fn from_tape(tape: &mut Tape<'input>) -> de::Result<Self>
     where
@@ -36,6 +36,19 @@ impl<'input> Deserialize<'input> for &'input str {
     }
 }
 
+impl<'input: 'cow, 'cow> Deserialize<'input> for std::borrow::Cow<'cow, str> {
+    #[inline]
+    fn from_tape(tape: &mut Tape<'input>) -> de::Result<Self>
+    where
+        Self: Sized + 'input,
+    {
+        match tape.next() {
+            Some(simd_json::Node::String(s)) => Ok(std::borrow::Cow::Borrowed(s)),
+            _ => Err(de::Error::expected_string()),
+        }
+    }
+}
+
 impl Serialize for str {
     #[inline]
     fn json_write<W>(&self, writer: &mut W) -> Result
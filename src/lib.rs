@@ -13,6 +13,13 @@ pub mod de;
 
 pub use de::Deserialize;
 
+/// The `simd_json` items derived code refers to, so crates using the derives
+/// don't need to depend on the same version of `simd_json` themselves.
+#[doc(hidden)]
+pub mod __private {
+    pub use simd_json::{Node, StaticNode};
+}
+
 pub fn __skip(n: usize, tape: &mut Tape) {
     for _ in 0..n {
         match tape.next() {
@@ -36,11 +43,132 @@ pub fn __skip(n: usize, tape: &mut Tape) {
     }
 }
 
+/// Takes the next value, including all nested values, from the tape so it can
+/// be read more than once.
+#[doc(hidden)]
+pub fn __subtree<'input>(tape: &mut Tape<'input>) -> Vec<Node<'input>> {
+    match tape.next() {
+        Some(node @ (Node::Array { count, .. } | Node::Object { count, .. })) => {
+            let mut nodes = Vec::with_capacity(count + 1);
+            nodes.push(node);
+            nodes.extend(tape.by_ref().take(count));
+            nodes
+        }
+        Some(node) => vec![node],
+        None => Vec::new(),
+    }
+}
+
+/// Reads a value from the `len` keys, and their values, in `nodes` that were
+/// not claimed by the other fields of the enclosing object.
+#[doc(hidden)]
+pub fn __flattened<'input, T>(len: usize, nodes: &[Node<'input>]) -> de::Result<T>
+where
+    T: Deserialize<'input> + 'input,
+{
+    T::__from_flattened(len, nodes)
+}
+
+/// Removes the `tag` key from the object at the head of the tape and returns
+/// its value along with a tape for the remaining object.
+#[doc(hidden)]
+pub fn __untag<'input>(
+    tape: &mut Tape<'input>,
+    tag: &'static str,
+) -> de::Result<(&'input str, Tape<'input>)> {
+    let Some(&Node::Object { len, count }) = tape.peek() else {
+        return Err(de::Error::InvalidEnumRepresentation);
+    };
+    let mut nodes = __subtree(tape);
+    let mut value = None;
+    let mut idx = 1;
+    for _ in 0..len {
+        let end = idx
+            + match nodes.get(idx + 1) {
+                Some(Node::Array { count, .. } | Node::Object { count, .. }) => count + 2,
+                Some(_) => 2,
+                None => return Err(de::Error::EOF),
+            };
+        if let Some(Node::String(key)) = nodes.get(idx) {
+            if *key == tag {
+                let Some(Node::String(v)) = nodes.get(idx + 1) else {
+                    return Err(de::Error::expected_string());
+                };
+                value = Some(*v);
+                nodes.drain(idx..end);
+                nodes[0] = Node::Object {
+                    len: len - 1,
+                    count: count - 2,
+                };
+                break;
+            }
+        }
+        idx = end;
+    }
+    let value = value.ok_or(de::Error::MissingField(tag))?;
+    Ok((value, nodes.into_iter().peekable()))
+}
+
+/// Reads an adjacently tagged object from the tape and returns the value of
+/// the `tag` key along with a tape for the value of the `content` key.
+#[doc(hidden)]
+pub fn __untag_adjacent<'input>(
+    tape: &mut Tape<'input>,
+    tag: &'static str,
+    content: &'static str,
+) -> de::Result<(&'input str, Option<Tape<'input>>)> {
+    let Some(Node::Object { len, .. }) = tape.next() else {
+        return Err(de::Error::InvalidEnumRepresentation);
+    };
+    let mut variant = None;
+    let mut value = None;
+    for _ in 0..len {
+        match tape.next() {
+            Some(Node::String(key)) if key == tag => match tape.next() {
+                Some(Node::String(v)) => variant = Some(v),
+                _ => return Err(de::Error::expected_string()),
+            },
+            Some(Node::String(key)) if key == content => {
+                value = Some(__subtree(tape).into_iter().peekable());
+            }
+            Some(_) => __skip(1, tape),
+            None => return Err(de::Error::EOF),
+        }
+    }
+    let variant = variant.ok_or(de::Error::MissingField(tag))?;
+    Ok((variant, value))
+}
+
 pub trait Serialize {
     fn json_write<W>(&self, writer: &mut W) -> Result
     where
         W: Write;
 
+    /// Writes the keys and values of this object without the surrounding
+    /// braces so they can be inlined into an enclosing object, returns
+    /// whether any key has been written.
+    #[doc(hidden)]
+    #[inline]
+    fn __json_write_fields<W>(&self, writer: &mut W, has_written_key: bool) -> io::Result<bool>
+    where
+        W: Write,
+    {
+        match self.json_vec()?.as_slice() {
+            b"{}" => Ok(has_written_key),
+            [b'{', fields @ .., b'}'] => {
+                if has_written_key {
+                    writer.write_all(b",")?;
+                }
+                writer.write_all(fields)?;
+                Ok(true)
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "only objects can be written inline",
+            )),
+        }
+    }
+
     #[inline]
     fn json_vec(&self) -> io::Result<Vec<u8>> {
         let mut v = Vec::with_capacity(512);
@@ -51,3 +51,177 @@ fn rename_all_camelcase() {
         b.json_string().unwrap()
     )
 }
+
+#[test]
+fn rename_all_fields() {
+    #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "PascalCase")]
+    struct Pascal {
+        field_one: u8,
+    }
+    #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    struct ScreamingSnake {
+        field_one: u8,
+    }
+    #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "kebab-case")]
+    struct Kebab {
+        field_one: u8,
+    }
+    #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+    struct ScreamingKebab {
+        field_one: u8,
+    }
+    #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "UPPERCASE")]
+    struct Upper {
+        field_one: u8,
+    }
+
+    let mut s = Pascal { field_one: 1 }.json_string().unwrap();
+    assert_eq!(r#"{"FieldOne":1}"#, s);
+    assert_eq!(
+        Pascal { field_one: 1 },
+        unsafe { Pascal::from_str(&mut s) }.unwrap()
+    );
+    let mut s = ScreamingSnake { field_one: 1 }.json_string().unwrap();
+    assert_eq!(r#"{"FIELD_ONE":1}"#, s);
+    assert_eq!(
+        ScreamingSnake { field_one: 1 },
+        unsafe { ScreamingSnake::from_str(&mut s) }.unwrap()
+    );
+    let mut s = Kebab { field_one: 1 }.json_string().unwrap();
+    assert_eq!(r#"{"field-one":1}"#, s);
+    assert_eq!(
+        Kebab { field_one: 1 },
+        unsafe { Kebab::from_str(&mut s) }.unwrap()
+    );
+    let mut s = ScreamingKebab { field_one: 1 }.json_string().unwrap();
+    assert_eq!(r#"{"FIELD-ONE":1}"#, s);
+    assert_eq!(
+        ScreamingKebab { field_one: 1 },
+        unsafe { ScreamingKebab::from_str(&mut s) }.unwrap()
+    );
+    let mut s = Upper { field_one: 1 }.json_string().unwrap();
+    assert_eq!(r#"{"FIELD_ONE":1}"#, s);
+    assert_eq!(
+        Upper { field_one: 1 },
+        unsafe { Upper::from_str(&mut s) }.unwrap()
+    );
+}
+
+#[test]
+fn rename_all_variants() {
+    #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "snake_case")]
+    enum Snake {
+        HttpRequest,
+        HTTPResponse,
+        Other(u8),
+    }
+    #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    enum ScreamingSnake {
+        HttpRequest,
+    }
+    #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "camelCase")]
+    enum Camel {
+        HttpRequest,
+    }
+    #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+    enum ScreamingKebab {
+        HttpRequest,
+    }
+
+    let mut s = Snake::HttpRequest.json_string().unwrap();
+    assert_eq!(r#""http_request""#, s);
+    assert_eq!(
+        Snake::HttpRequest,
+        unsafe { Snake::from_str(&mut s) }.unwrap()
+    );
+    // every uppercase letter starts a new word, just like serde does it
+    let mut s = Snake::HTTPResponse.json_string().unwrap();
+    assert_eq!(r#""h_t_t_p_response""#, s);
+    assert_eq!(
+        Snake::HTTPResponse,
+        unsafe { Snake::from_str(&mut s) }.unwrap()
+    );
+    let mut s = Snake::Other(1).json_string().unwrap();
+    assert_eq!(r#"{"other":1}"#, s);
+    assert_eq!(Snake::Other(1), unsafe { Snake::from_str(&mut s) }.unwrap());
+
+    let mut s = ScreamingSnake::HttpRequest.json_string().unwrap();
+    assert_eq!(r#""HTTP_REQUEST""#, s);
+    assert_eq!(
+        ScreamingSnake::HttpRequest,
+        unsafe { ScreamingSnake::from_str(&mut s) }.unwrap()
+    );
+    let mut s = Camel::HttpRequest.json_string().unwrap();
+    assert_eq!(r#""httpRequest""#, s);
+    assert_eq!(
+        Camel::HttpRequest,
+        unsafe { Camel::from_str(&mut s) }.unwrap()
+    );
+    let mut s = ScreamingKebab::HttpRequest.json_string().unwrap();
+    assert_eq!(r#""HTTP-REQUEST""#, s);
+    assert_eq!(
+        ScreamingKebab::HttpRequest,
+        unsafe { ScreamingKebab::from_str(&mut s) }.unwrap()
+    );
+}
+
+#[test]
+fn rename_serialize_deserialize() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(rename_all(serialize = "SCREAMING_SNAKE_CASE", deserialize = "camelCase"))]
+    struct Bla {
+        #[serde(rename(serialize = "out", deserialize = "in"))]
+        field_one: u8,
+        field_two: u8,
+        #[simd_json(rename(deserialize = "three"))]
+        field_three: u8,
+    }
+
+    let b = Bla {
+        field_one: 1,
+        field_two: 2,
+        field_three: 3,
+    };
+    assert_eq!(
+        r#"{"out":1,"FIELD_TWO":2,"FIELD_THREE":3}"#,
+        b.json_string().unwrap()
+    );
+    let mut s = r#"{"in":1,"fieldTwo":2,"three":3}"#.to_string();
+    assert_eq!(b, unsafe { Bla::from_str(&mut s) }.unwrap());
+}
+
+#[test]
+fn rename_all_fields_on_enum() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "snake_case", rename_all_fields = "camelCase")]
+    enum Event {
+        UserCreated {
+            user_name: String,
+        },
+        #[serde(rename(serialize = "gone", deserialize = "deleted"))]
+        UserDeleted {
+            user_id: u8,
+        },
+    }
+
+    let e = Event::UserCreated {
+        user_name: "snot".into(),
+    };
+    let mut s = e.json_string().unwrap();
+    assert_eq!(r#"{"user_created":{"userName":"snot"}}"#, s);
+    assert_eq!(e, unsafe { Event::from_str(&mut s) }.unwrap());
+
+    let e = Event::UserDeleted { user_id: 1 };
+    assert_eq!(r#"{"gone":{"userId":1}}"#, e.json_string().unwrap());
+    let mut s = r#"{"deleted":{"userId":1}}"#.to_string();
+    assert_eq!(e, unsafe { Event::from_str(&mut s) }.unwrap());
+}
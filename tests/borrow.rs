@@ -0,0 +1,58 @@
+use simd_json_derive::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+#[test]
+fn cow_fields() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Message<'a> {
+        #[serde(borrow)]
+        name: Cow<'a, str>,
+        #[simd_json(borrow)]
+        note: Option<Cow<'a, str>>,
+        data: Cow<'a, [u8]>,
+    }
+
+    let mut s = r#"{"name":"snot","note":"bad\"ger","data":[1,2]}"#.to_string();
+    let m = unsafe { Message::from_str(s.as_mut_str()) }.unwrap();
+    assert!(matches!(m.name, Cow::Borrowed("snot")));
+    assert!(matches!(m.note, Some(Cow::Borrowed("bad\"ger"))));
+    assert_eq!(m.data, Cow::<[u8]>::Owned(vec![1, 2]));
+    assert_eq!(
+        r#"{"name":"snot","note":"bad\"ger","data":[1,2]}"#,
+        m.json_string().unwrap()
+    );
+
+    let m = Message {
+        name: Cow::Owned("owned".into()),
+        note: None,
+        data: Cow::Borrowed(&[]),
+    };
+    assert_eq!(
+        r#"{"name":"owned","note":null,"data":[]}"#,
+        m.json_string().unwrap()
+    );
+}
+
+#[test]
+fn borrow_other_lifetime() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Inner<'i> {
+        name: &'i str,
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Outer<'o, 'i> {
+        id: &'o str,
+        #[serde(borrow = "'i")]
+        inner: Inner<'i>,
+    }
+
+    let mut s = r#"{"id":"snot","inner":{"name":"badger"}}"#.to_string();
+    assert_eq!(
+        Outer {
+            id: "snot",
+            inner: Inner { name: "badger" }
+        },
+        unsafe { Outer::from_str(s.as_mut_str()) }.unwrap()
+    );
+}
@@ -0,0 +1,70 @@
+use simd_json_derive::{Deserialize, Serialize};
+
+#[test]
+fn try_from_and_into() {
+    #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+    #[serde(try_from = "u8", into = "u8")]
+    struct Percent(u8);
+
+    impl TryFrom<u8> for Percent {
+        type Error = String;
+        fn try_from(v: u8) -> Result<Self, Self::Error> {
+            if v <= 100 {
+                Ok(Percent(v))
+            } else {
+                Err(format!("{v} is not a percentage"))
+            }
+        }
+    }
+
+    impl From<Percent> for u8 {
+        fn from(p: Percent) -> u8 {
+            p.0
+        }
+    }
+
+    let mut s = Percent(42).json_string().unwrap();
+    assert_eq!("42", s);
+    assert_eq!(Percent(42), unsafe { Percent::from_str(&mut s) }.unwrap());
+
+    let mut s = "101".to_string();
+    let err = unsafe { Percent::from_str(&mut s) }.unwrap_err();
+    assert_eq!(err.to_string(), "Custom error: 101 is not a percentage");
+}
+
+#[test]
+fn from_and_into() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Wire {
+        first: String,
+        last: String,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+    #[simd_json(from = "Wire", into = "Wire")]
+    enum Name {
+        Full(String),
+    }
+
+    impl From<Wire> for Name {
+        fn from(w: Wire) -> Self {
+            Name::Full(format!("{} {}", w.first, w.last))
+        }
+    }
+
+    impl From<Name> for Wire {
+        fn from(n: Name) -> Self {
+            let Name::Full(full) = n;
+            let (first, last) = full.split_once(' ').unwrap_or((&full, ""));
+            Wire {
+                first: first.to_string(),
+                last: last.to_string(),
+            }
+        }
+    }
+
+    let n = Name::Full("snot badger".to_string());
+    let mut s = n.json_string().unwrap();
+    assert_eq!(r#"{"first":"snot","last":"badger"}"#, s);
+    assert_eq!(n, unsafe { Name::from_str(&mut s) }.unwrap());
+}
@@ -22,4 +22,109 @@ fn default_in_named_struct() {
     let mut s = r#"{"f1":0}"#.to_string();
     let b1 = unsafe { Bla::from_str(s.as_mut_str()) };
     assert!(b1.is_err());
-}
\ No newline at end of file
+}
+
+#[test]
+fn default_path_in_named_struct() {
+    fn default_port() -> u16 {
+        8080
+    }
+
+    #[derive(simd_json_derive::Deserialize, PartialEq, Debug)]
+    struct Server {
+        host: String,
+        #[simd_json(default = "default_port")]
+        port: u16,
+    }
+
+    let mut s = r#"{"host":"localhost"}"#.to_string();
+    let b = Server { host: "localhost".into(), port: 8080 };
+    let b1 = unsafe { Server::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(b, b1);
+
+    let mut s = r#"{"host":"localhost","port":80}"#.to_string();
+    let b = Server { host: "localhost".into(), port: 80 };
+    let b1 = unsafe { Server::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(b, b1);
+}
+
+#[test]
+fn default_on_container() {
+    #[derive(simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(default)]
+    struct Server {
+        host: String,
+        port: u16,
+        #[serde(default)]
+        retries: u8,
+        timeout: Option<u32>,
+    }
+
+    impl Default for Server {
+        fn default() -> Self {
+            Server { host: "localhost".into(), port: 8080, retries: 3, timeout: Some(30) }
+        }
+    }
+
+    let mut s = r#"{"host":"example.com"}"#.to_string();
+    let b = Server { host: "example.com".into(), port: 8080, retries: 0, timeout: Some(30) };
+    let b1 = unsafe { Server::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(b, b1);
+
+    let mut s = r#"{"port":80,"timeout":null}"#.to_string();
+    let b = Server { host: "localhost".into(), port: 80, retries: 0, timeout: None };
+    let b1 = unsafe { Server::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(b, b1);
+}
+
+#[test]
+fn default_path_on_container() {
+    fn fallback() -> Server {
+        Server { host: "fallback".into(), port: 1 }
+    }
+
+    #[derive(simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(default = "fallback")]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    let mut s = r#"{"port":2}"#.to_string();
+    let b = Server { host: "fallback".into(), port: 2 };
+    let b1 = unsafe { Server::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(b, b1);
+}
+
+#[test]
+fn default_in_transparent_struct() {
+    fn default_hits() -> u32 {
+        7
+    }
+
+    #[derive(simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(transparent)]
+    struct Counted {
+        name: String,
+        #[serde(skip, default = "default_hits")]
+        hits: u32,
+    }
+
+    let mut s = r#""badger""#.to_string();
+    let b = Counted { name: "badger".into(), hits: 7 };
+    let b1 = unsafe { Counted::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(b, b1);
+
+    fn fallback() -> Cached {
+        Cached(String::new(), Some(3))
+    }
+
+    #[derive(simd_json_derive::Deserialize, PartialEq, Debug)]
+    #[serde(transparent, default = "fallback")]
+    struct Cached(String, #[serde(skip)] Option<usize>);
+
+    let mut s = r#""snot""#.to_string();
+    let b = Cached("snot".into(), Some(3));
+    let b1 = unsafe { Cached::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(b, b1);
+}
@@ -1,4 +1,4 @@
-use simd_json_derive::Serialize;
+use simd_json_derive::{Deserialize, Serialize};
 
 #[test]
 fn enum_stuff_01() {
@@ -73,3 +73,169 @@ fn enum_ser() {
 
     // let e = StoredVariants::from_str(s.as_mut_str()).unwrap();
 }
+
+#[test]
+fn enum_named_deser() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "lowercase")]
+    enum Command {
+        Stop,
+        Move {
+            #[serde(rename = "x")]
+            pos_x: u8,
+            pos_y: u16,
+            #[serde(default)]
+            speed: u8,
+            label: Option<String>,
+        },
+    }
+
+    let c = Command::Move {
+        pos_x: 1,
+        pos_y: 2,
+        speed: 3,
+        label: Some("snot".into()),
+    };
+    let mut s = c.json_string().unwrap();
+    assert_eq!(r#"{"move":{"x":1,"pos_y":2,"speed":3,"label":"snot"}}"#, s);
+    let c1 = unsafe { Command::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(c, c1);
+
+    let mut s = r#"{"move":{"pos_y":2,"unknown":[1,2],"x":1}}"#.to_string();
+    let c1 = unsafe { Command::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(
+        Command::Move {
+            pos_x: 1,
+            pos_y: 2,
+            speed: 0,
+            label: None
+        },
+        c1
+    );
+
+    let mut s = r#"{"move":{"x":1}}"#.to_string();
+    let err = unsafe { Command::from_str(s.as_mut_str()) }.unwrap_err();
+    assert_eq!(err.to_string(), "missing field: `pos_y`");
+
+    let mut s = r#""stop""#.to_string();
+    let c1 = unsafe { Command::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(Command::Stop, c1);
+}
+
+#[test]
+fn enum_named_deny_unknown_fields() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[simd_json(deny_unknown_fields)]
+    enum Strict {
+        Point { x: u8, y: u8 },
+    }
+
+    let mut s = r#"{"Point":{"x":1,"y":2}}"#.to_string();
+    let p = unsafe { Strict::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(Strict::Point { x: 1, y: 2 }, p);
+
+    let mut s = r#"{"Point":{"x":1,"z":3,"y":2}}"#.to_string();
+    let err = unsafe { Strict::from_str(s.as_mut_str()) }.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown field `z`, expected one of `x`, `y`"
+    );
+}
+
+#[test]
+fn other_variant() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Status {
+        Active,
+        Retired {
+            since: u16,
+        },
+        #[simd_json(other)]
+        Unknown,
+    }
+
+    let mut s = r#""Active""#.to_string();
+    assert_eq!(Status::Active, unsafe { Status::from_str(&mut s) }.unwrap());
+    let mut s = r#""Suspended""#.to_string();
+    assert_eq!(
+        Status::Unknown,
+        unsafe { Status::from_str(&mut s) }.unwrap()
+    );
+    let mut s = r#"{"Archived":{"at":[1,2]}}"#.to_string();
+    assert_eq!(
+        Status::Unknown,
+        unsafe { Status::from_str(&mut s) }.unwrap()
+    );
+    let mut s = r#"{"Retired":{"since":2020}}"#.to_string();
+    assert_eq!(
+        Status::Retired { since: 2020 },
+        unsafe { Status::from_str(&mut s) }.unwrap()
+    );
+}
+
+#[test]
+fn other_variant_keeps_name() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "snake_case")]
+    enum Kind<'input> {
+        Created,
+        Deleted,
+        #[serde(other)]
+        Unknown(&'input str),
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(tag = "type")]
+    enum Event {
+        Created {
+            id: u8,
+        },
+        #[serde(other)]
+        Unknown(String),
+    }
+
+    let mut s = r#"["created","moved"]"#.to_string();
+    let kinds = unsafe { Vec::<Kind>::from_str(&mut s) }.unwrap();
+    assert_eq!(vec![Kind::Created, Kind::Unknown("moved")], kinds);
+    assert_eq!(r#"["created","moved"]"#, kinds.json_string().unwrap());
+
+    let mut s = r#"{"type":"Renamed","id":1}"#.to_string();
+    let e = unsafe { Event::from_str(&mut s) }.unwrap();
+    assert_eq!(Event::Unknown("Renamed".to_string()), e);
+    assert_eq!(r#"{"type":"Renamed"}"#, e.json_string().unwrap());
+}
+
+#[test]
+fn repr() {
+    #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+    #[simd_json(repr = "u8")]
+    enum Level {
+        Debug = 10,
+        Info = 20,
+        Warn,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(repr = "i16")]
+    enum Status {
+        Ok = 0,
+        Failed = -1,
+        #[serde(other)]
+        Unknown = 99,
+    }
+
+    let levels = vec![Level::Debug, Level::Info, Level::Warn];
+    let mut s = levels.json_string().unwrap();
+    assert_eq!("[10,20,21]", s);
+    assert_eq!(levels, unsafe { Vec::<Level>::from_str(&mut s) }.unwrap());
+
+    let mut s = "30".to_string();
+    let err = unsafe { Level::from_str(&mut s) }.unwrap_err();
+    assert_eq!(err.to_string(), "unknwon enum variant `30`");
+
+    let mut s = "[-1,0,7]".to_string();
+    assert_eq!(
+        vec![Status::Failed, Status::Ok, Status::Unknown],
+        unsafe { Vec::<Status>::from_str(&mut s) }.unwrap()
+    );
+}
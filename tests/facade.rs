@@ -0,0 +1,64 @@
+/// Stands in for a crate re-exporting the derives
+mod facade {
+    pub use simd_json_derive::*;
+}
+
+use facade::{Deserialize, Serialize};
+
+#[test]
+fn crate_path() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[simd_json(crate = "crate::facade")]
+    struct Point {
+        x: i8,
+        #[serde(default)]
+        y: Option<i8>,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[simd_json(crate = "crate::facade", tag = "type")]
+    enum Shape {
+        Dot(Point),
+        Circle { center: Point, radius: u8 },
+        Empty,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[simd_json(crate = "crate::facade")]
+    struct Drawing(Vec<Shape>, ());
+
+    let d = Drawing(
+        vec![
+            Shape::Dot(Point { x: 1, y: None }),
+            Shape::Circle {
+                center: Point { x: 0, y: Some(2) },
+                radius: 3,
+            },
+            Shape::Empty,
+        ],
+        (),
+    );
+    let mut s = d.json_string().unwrap();
+    assert_eq!(
+        r#"[[{"type":"Dot","x":1,"y":null},{"type":"Circle","center":{"x":0,"y":2},"radius":3},{"type":"Empty"}],null]"#,
+        s
+    );
+    assert_eq!(d, unsafe { Drawing::from_str(s.as_mut_str()) }.unwrap());
+}
+
+#[test]
+fn serde_crate_path_is_ignored() {
+    // `crate` in `#[serde(..)]` is the path of serde, not of this crate
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(crate = "crate::not_serde")]
+    struct Point {
+        x: i8,
+    }
+
+    let mut s = Point { x: 1 }.json_string().unwrap();
+    assert_eq!(r#"{"x":1}"#, s);
+    assert_eq!(
+        Point { x: 1 },
+        unsafe { Point::from_str(s.as_mut_str()) }.unwrap()
+    );
+}
@@ -0,0 +1,208 @@
+use simd_json_derive::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Envelope {
+    id: u64,
+    created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+}
+
+#[test]
+fn flatten_struct() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct User {
+        name: String,
+        #[simd_json(flatten)]
+        envelope: Envelope,
+        admin: bool,
+    }
+
+    let u = User {
+        name: "snot".into(),
+        envelope: Envelope {
+            id: 1,
+            created_at: "today".into(),
+            etag: None,
+        },
+        admin: true,
+    };
+    let mut s = u.json_string().unwrap();
+    assert_eq!(
+        r#"{"name":"snot","id":1,"created_at":"today","admin":true}"#,
+        s
+    );
+    assert_eq!(u, unsafe { User::from_str(s.as_mut_str()) }.unwrap());
+
+    let mut s =
+        r#"{"etag":"abc","admin":false,"id":2,"name":"badger","created_at":"now"}"#.to_string();
+    assert_eq!(
+        User {
+            name: "badger".into(),
+            envelope: Envelope {
+                id: 2,
+                created_at: "now".into(),
+                etag: Some("abc".into()),
+            },
+            admin: false,
+        },
+        unsafe { User::from_str(s.as_mut_str()) }.unwrap()
+    );
+
+    let mut s = r#"{"admin":false,"name":"badger","created_at":"now"}"#.to_string();
+    let err = unsafe { User::from_str(s.as_mut_str()) }.unwrap_err();
+    assert_eq!(err.to_string(), "missing field: `id`");
+}
+
+#[test]
+fn flatten_in_enum() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Resource {
+        Repo {
+            #[serde(flatten)]
+            envelope: Envelope,
+            stars: u32,
+        },
+    }
+
+    let r = Resource::Repo {
+        envelope: Envelope {
+            id: 7,
+            created_at: "yesterday".into(),
+            etag: Some("xyz".into()),
+        },
+        stars: 3,
+    };
+    let mut s = r.json_string().unwrap();
+    assert_eq!(
+        r#"{"Repo":{"id":7,"created_at":"yesterday","etag":"xyz","stars":3}}"#,
+        s
+    );
+    assert_eq!(r, unsafe { Resource::from_str(s.as_mut_str()) }.unwrap());
+}
+
+#[test]
+fn extra_fields() {
+    use simd_json::{BorrowedValue, OwnedValue};
+    use std::collections::HashMap;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Proxy<'input> {
+        id: u64,
+        #[simd_json(flatten)]
+        rest: HashMap<&'input str, BorrowedValue<'input>>,
+    }
+
+    let mut s = r#"{"id":1,"snot":[1,{"badger":true}]}"#.to_string();
+    let p = Proxy::from_slice(unsafe { s.as_bytes_mut() }).unwrap();
+    assert_eq!(1, p.id);
+    assert_eq!(1, p.rest.len());
+    assert_eq!(
+        r#"[1,{"badger":true}]"#,
+        p.rest.get("snot").unwrap().json_string().unwrap()
+    );
+    assert_eq!(
+        r#"{"id":1,"snot":[1,{"badger":true}]}"#,
+        p.json_string().unwrap()
+    );
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Gateway {
+        #[serde(flatten)]
+        envelope: Envelope,
+        kind: String,
+        #[simd_json(extra)]
+        extra: HashMap<String, OwnedValue>,
+    }
+
+    let mut s = r#"{"kind":"user","id":2,"created_at":"now","name":"snot","age":42}"#.to_string();
+    let g = unsafe { Gateway::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(
+        Envelope {
+            id: 2,
+            created_at: "now".into(),
+            etag: None
+        },
+        g.envelope
+    );
+    assert_eq!("user", g.kind);
+    assert_eq!(2, g.extra.len());
+    assert_eq!(Some(&OwnedValue::from("snot")), g.extra.get("name"));
+    assert_eq!(Some(&OwnedValue::from(42)), g.extra.get("age"));
+    let mut s = g.json_string().unwrap();
+    let g1 = unsafe { Gateway::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(g, g1);
+}
+
+#[test]
+fn flatten_struct_and_map() {
+    use std::collections::HashMap;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct FlatBoth {
+        name: String,
+        #[simd_json(flatten)]
+        envelope: Envelope,
+        #[simd_json(flatten)]
+        rest: HashMap<String, u32>,
+    }
+
+    let mut s = r#"{"name":"n","id":3,"created_at":"now","other":4}"#.to_string();
+    let f = unsafe { FlatBoth::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(
+        Envelope {
+            id: 3,
+            created_at: "now".into(),
+            etag: None
+        },
+        f.envelope
+    );
+    assert_eq!(HashMap::from([("other".to_string(), 4)]), f.rest);
+    assert_eq!(
+        r#"{"name":"n","id":3,"created_at":"now","other":4}"#,
+        f.json_string().unwrap()
+    );
+}
+
+#[test]
+fn flatten_option() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Entry {
+        name: String,
+        #[simd_json(flatten)]
+        envelope: Option<Envelope>,
+    }
+
+    let e = Entry {
+        name: "snot".into(),
+        envelope: None,
+    };
+    let mut s = e.json_string().unwrap();
+    assert_eq!(r#"{"name":"snot"}"#, s);
+    assert_eq!(e, unsafe { Entry::from_str(s.as_mut_str()) }.unwrap());
+
+    let e = Entry {
+        name: "snot".into(),
+        envelope: Some(Envelope {
+            id: 1,
+            created_at: "now".into(),
+            etag: None,
+        }),
+    };
+    let mut s = e.json_string().unwrap();
+    assert_eq!(r#"{"name":"snot","id":1,"created_at":"now"}"#, s);
+    assert_eq!(e, unsafe { Entry::from_str(s.as_mut_str()) }.unwrap());
+
+    // unknown keys don't make the envelope present, a partial one is still an error
+    let mut s = r#"{"name":"snot","other":[1,{"id":2}]}"#.to_string();
+    assert_eq!(
+        Entry {
+            name: "snot".into(),
+            envelope: None,
+        },
+        unsafe { Entry::from_str(s.as_mut_str()) }.unwrap()
+    );
+    let mut s = r#"{"name":"snot","id":2}"#.to_string();
+    let err = unsafe { Entry::from_str(s.as_mut_str()) }.unwrap_err();
+    assert_eq!(err.to_string(), "missing field: `created_at`");
+}
@@ -0,0 +1,141 @@
+use simd_json_derive::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[test]
+fn inferred_bounds() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Page<T> {
+        items: Vec<T>,
+        next: Option<u32>,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Either<L, R> {
+        Left(L),
+        Right { value: R },
+    }
+
+    let p = Page {
+        items: vec![
+            Either::Left(1u8),
+            Either::Right {
+                value: "snot".to_string(),
+            },
+        ],
+        next: Some(2),
+    };
+    let mut s = p.json_string().unwrap();
+    assert_eq!(
+        r#"{"items":[{"Left":1},{"Right":{"value":"snot"}}],"next":2}"#,
+        s
+    );
+    assert_eq!(p, unsafe { Page::from_str(s.as_mut_str()) }.unwrap());
+}
+
+#[test]
+fn where_clause_and_phantom() {
+    struct NotSerializable;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Tagged<T, M>
+    where
+        T: Clone,
+    {
+        value: T,
+        #[serde(skip)]
+        marker: PhantomData<M>,
+    }
+
+    impl PartialEq for NotSerializable {
+        fn eq(&self, _: &Self) -> bool {
+            true
+        }
+    }
+    impl Debug for NotSerializable {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("NotSerializable")
+        }
+    }
+
+    let t: Tagged<u8, NotSerializable> = Tagged {
+        value: 7,
+        marker: PhantomData,
+    };
+    let mut s = t.json_string().unwrap();
+    assert_eq!(r#"{"value":7}"#, s);
+    assert_eq!(t, unsafe { Tagged::from_str(s.as_mut_str()) }.unwrap());
+}
+
+#[test]
+fn const_generics() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Fixed<const N: usize> {
+        values: [u8; N],
+    }
+
+    let f = Fixed { values: [1, 2, 3] };
+    let mut s = f.json_string().unwrap();
+    assert_eq!(r#"{"values":[1,2,3]}"#, s);
+    assert_eq!(f, unsafe { Fixed::<3>::from_str(s.as_mut_str()) }.unwrap());
+}
+
+#[test]
+fn several_lifetimes() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Borrowed<'a, 'b, T> {
+        first: &'a str,
+        second: &'b str,
+        rest: T,
+    }
+
+    let mut s = r#"{"first":"snot","second":"badger","rest":1}"#.to_string();
+    let b = unsafe { Borrowed::<u8>::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(
+        Borrowed {
+            first: "snot",
+            second: "badger",
+            rest: 1
+        },
+        b
+    );
+}
+
+#[test]
+fn custom_bound() {
+    trait Entity {
+        type Id;
+    }
+    struct User;
+    impl Entity for User {
+        type Id = u64;
+    }
+
+    // the inferred `T: Serialize` bound would not hold for `User`
+    #[derive(Serialize, Deserialize)]
+    #[simd_json(bound(
+        serialize = "T::Id: Serialize",
+        deserialize = "T::Id: Deserialize<'input>"
+    ))]
+    struct Ref<T: Entity> {
+        id: T::Id,
+    }
+
+    let r: Ref<User> = Ref { id: 42 };
+    let mut s = r.json_string().unwrap();
+    assert_eq!(r#"{"id":42}"#, s);
+    let r = unsafe { Ref::<User>::from_str(s.as_mut_str()) }.unwrap();
+    assert_eq!(42, r.id);
+
+    // a `bound` in `#[serde(..)]` is for serde's traits so the inferred bounds are kept
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(bound = "T: Default")]
+    struct Wrapper<T> {
+        value: T,
+    }
+
+    let w = Wrapper { value: 7u8 };
+    let mut s = w.json_string().unwrap();
+    assert_eq!(r#"{"value":7}"#, s);
+    assert_eq!(w, unsafe { Wrapper::from_str(s.as_mut_str()) }.unwrap());
+}
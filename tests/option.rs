@@ -0,0 +1,90 @@
+use simd_json_derive::{Deserialize, Serialize};
+
+type Maybe<T> = Option<T>;
+
+#[test]
+fn option_paths() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Paths {
+        plain: Option<u8>,
+        std: std::option::Option<u8>,
+        core: ::core::option::Option<u8>,
+        #[simd_json(optional)]
+        alias: Maybe<u8>,
+    }
+
+    let mut s = r#"{}"#.to_string();
+    assert_eq!(
+        Paths {
+            plain: None,
+            std: None,
+            core: None,
+            alias: None,
+        },
+        unsafe { Paths::from_str(s.as_mut_str()) }.unwrap()
+    );
+
+    let mut s = r#"{"plain":1,"std":null,"core":3,"alias":4}"#.to_string();
+    assert_eq!(
+        Paths {
+            plain: Some(1),
+            std: None,
+            core: Some(3),
+            alias: Some(4),
+        },
+        unsafe { Paths::from_str(s.as_mut_str()) }.unwrap()
+    );
+}
+
+#[test]
+fn double_option() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Patch {
+        name: Option<Option<String>>,
+        age: Option<Option<u8>>,
+        email: Option<Option<String>>,
+    }
+
+    let mut s = r#"{"name":"snot","age":null}"#.to_string();
+    assert_eq!(
+        Patch {
+            name: Some(Some("snot".into())),
+            age: Some(None),
+            email: None,
+        },
+        unsafe { Patch::from_str(s.as_mut_str()) }.unwrap()
+    );
+}
+
+#[test]
+fn double_option_with_default() {
+    fn unchanged() -> Option<Option<String>> {
+        None
+    }
+
+    #[derive(Deserialize, PartialEq, Debug, Default)]
+    #[simd_json(default)]
+    struct Patch {
+        age: Option<Option<u8>>,
+        #[simd_json(default = "unchanged")]
+        email: Option<Option<String>>,
+    }
+
+    let mut s = r#"{"age":null,"email":null}"#.to_string();
+    assert_eq!(
+        Patch {
+            age: Some(None),
+            email: Some(None),
+        },
+        unsafe { Patch::from_str(s.as_mut_str()) }.unwrap()
+    );
+
+    let mut s = r#"{"age":5}"#.to_string();
+    assert_eq!(
+        Patch {
+            age: Some(Some(5)),
+            email: None,
+        },
+        unsafe { Patch::from_str(s.as_mut_str()) }.unwrap()
+    );
+}
@@ -0,0 +1,139 @@
+use simd_json_derive::{Deserialize, Serialize};
+
+/// Stands in for a crate the types are defined in
+mod other {
+    #[derive(PartialEq, Debug)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    #[derive(PartialEq, Debug)]
+    pub struct Meters(pub f64);
+
+    #[derive(PartialEq, Debug)]
+    pub enum Shape {
+        Circle { radius: u8 },
+        Line(u8, u8),
+        Dot,
+    }
+
+    #[derive(PartialEq, Debug, Clone, Copy)]
+    pub enum Level {
+        Low = 1,
+        High = 10,
+    }
+
+    pub struct Account {
+        id: u64,
+        name: String,
+    }
+
+    impl Account {
+        pub fn new(id: u64, name: &str) -> Self {
+            Self {
+                id,
+                name: name.to_string(),
+            }
+        }
+        pub fn id(&self) -> u64 {
+            self.id
+        }
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "other::Point")]
+struct PointDef {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[simd_json(remote = "other::Meters")]
+struct MetersDef(f64);
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "other::Shape", rename_all = "lowercase")]
+enum ShapeDef {
+    Circle { radius: u8 },
+    Line(u8, u8),
+    Dot,
+}
+
+#[derive(Serialize)]
+#[serde(remote = "other::Account")]
+struct AccountDef {
+    #[serde(getter = "other::Account::id")]
+    id: u64,
+    #[serde(getter = "other::Account::name")]
+    name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[simd_json(remote = "other::Level", repr = "u8")]
+enum LevelDef {
+    Low = 1,
+    High = 10,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Drawing {
+    #[serde(with = "PointDef")]
+    origin: other::Point,
+    #[serde(with = "MetersDef")]
+    scale: other::Meters,
+    #[serde(with = "ShapeDef")]
+    shape: other::Shape,
+}
+
+#[test]
+fn remote() {
+    let d = Drawing {
+        origin: other::Point { x: 1, y: -2 },
+        scale: other::Meters(0.5),
+        shape: other::Shape::Circle { radius: 3 },
+    };
+    let mut s = d.json_string().unwrap();
+    assert_eq!(
+        r#"{"origin":{"x":1,"y":-2},"scale":0.5,"shape":{"circle":{"radius":3}}}"#,
+        s
+    );
+    assert_eq!(d, unsafe { Drawing::from_str(s.as_mut_str()) }.unwrap());
+
+    let mut s = r#"{"origin":{"x":0,"y":0},"scale":2.0,"shape":{"line":[1,2]}}"#.to_string();
+    assert_eq!(
+        Drawing {
+            origin: other::Point { x: 0, y: 0 },
+            scale: other::Meters(2.0),
+            shape: other::Shape::Line(1, 2),
+        },
+        unsafe { Drawing::from_str(s.as_mut_str()) }.unwrap()
+    );
+}
+
+#[test]
+fn remote_getter() {
+    let mut writer = Vec::new();
+    AccountDef::json_write(&other::Account::new(7, "snot"), &mut writer).unwrap();
+    assert_eq!(br#"{"id":7,"name":"snot"}"#, writer.as_slice());
+}
+
+#[test]
+fn remote_repr() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Alert {
+        #[serde(with = "LevelDef")]
+        level: other::Level,
+    }
+
+    let a = Alert {
+        level: other::Level::High,
+    };
+    let mut s = a.json_string().unwrap();
+    assert_eq!(r#"{"level":10}"#, s);
+    assert_eq!(a, unsafe { Alert::from_str(s.as_mut_str()) }.unwrap());
+}
@@ -1,4 +1,4 @@
-use simd_json_derive::Deserialize;
+use simd_json_derive::{Deserialize, Serialize};
 
 #[test]
 fn opt() {
@@ -19,4 +19,47 @@ fn opt() {
         },
         de
     );
-}
\ No newline at end of file
+}
+
+#[test]
+fn alias() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "camelCase")]
+    struct Account {
+        #[serde(alias = "userName", alias = "login")]
+        name: String,
+        #[simd_json(alias = "mail")]
+        email: Option<String>,
+        kind: Kind,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Kind {
+        #[serde(alias = "Administrator")]
+        Admin,
+        #[serde(alias = "Restricted")]
+        Guest(u8),
+    }
+
+    let expected = Account {
+        name: "snot".to_string(),
+        email: Some("snot@badger".to_string()),
+        kind: Kind::Admin,
+    };
+    let mut s =
+        r#"{"userName": "snot", "mail": "snot@badger", "kind": "Administrator"}"#.to_string();
+    let de = unsafe { Account::from_str(s.as_mut_str()) }.expect("expected alias to be read");
+    assert_eq!(expected, de);
+
+    let mut s = r#"{"login": "snot", "email": "snot@badger", "kind": "Admin"}"#.to_string();
+    let de = unsafe { Account::from_str(s.as_mut_str()) }.expect("expected alias to be read");
+    assert_eq!(expected, de);
+    assert_eq!(
+        r#"{"name":"snot","email":"snot@badger","kind":"Admin"}"#,
+        de.json_string().unwrap()
+    );
+
+    let mut s = r#"{"Restricted": 1}"#.to_string();
+    let de = unsafe { Kind::from_str(s.as_mut_str()) }.expect("expected alias to be read");
+    assert_eq!(Kind::Guest(1), de);
+}
@@ -0,0 +1,136 @@
+use simd_json_derive::{Deserialize, Serialize};
+
+#[test]
+fn skip_in_struct() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Cached {
+        pattern: String,
+        #[simd_json(skip)]
+        compiled: Option<Vec<u8>>,
+        #[serde(skip_serializing)]
+        hash: u64,
+        #[serde(skip_deserializing)]
+        len: usize,
+    }
+
+    let c = Cached {
+        pattern: "snot".into(),
+        compiled: Some(vec![1, 2]),
+        hash: 42,
+        len: 4,
+    };
+    let mut s = c.json_string().unwrap();
+    assert_eq!(r#"{"pattern":"snot","len":4}"#, s);
+    let err = unsafe { Cached::from_str(s.as_mut_str()) }.unwrap_err();
+    assert_eq!(err.to_string(), "missing field: `hash`");
+
+    let mut s = r#"{"pattern":"snot","compiled":[1],"hash":7,"len":4}"#.to_string();
+    assert_eq!(
+        Cached {
+            pattern: "snot".into(),
+            compiled: None,
+            hash: 7,
+            len: 0,
+        },
+        unsafe { Cached::from_str(s.as_mut_str()) }.unwrap()
+    );
+}
+
+#[test]
+fn skip_in_enum() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Rule {
+        Match {
+            pattern: String,
+            #[serde(skip)]
+            compiled: Vec<u8>,
+        },
+    }
+
+    let r = Rule::Match {
+        pattern: "snot".into(),
+        compiled: vec![1, 2, 3],
+    };
+    let mut s = r.json_string().unwrap();
+    assert_eq!(r#"{"Match":{"pattern":"snot"}}"#, s);
+    assert_eq!(
+        Rule::Match {
+            pattern: "snot".into(),
+            compiled: Vec::new()
+        },
+        unsafe { Rule::from_str(s.as_mut_str()) }.unwrap()
+    );
+}
+
+#[test]
+fn skip_deny_unknown_fields() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[serde(deny_unknown_fields)]
+    struct Strict {
+        name: String,
+        #[serde(skip_deserializing)]
+        derived: u8,
+    }
+
+    let mut s = r#"{"name":"snot","derived":1}"#.to_string();
+    let err = unsafe { Strict::from_str(s.as_mut_str()) }.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown field `derived`, expected one of `name`"
+    );
+}
+
+#[test]
+fn skip_all_fields() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct AllSkipped {
+        #[simd_json(skip)]
+        a: u8,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Empty {}
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Outer {
+        x: u8,
+        inner: AllSkipped,
+        empty: Empty,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Rule {
+        Match {
+            #[serde(skip)]
+            compiled: Vec<u8>,
+        },
+    }
+
+    let o = Outer {
+        x: 1,
+        inner: AllSkipped { a: 2 },
+        empty: Empty {},
+    };
+    let mut s = o.json_string().unwrap();
+    assert_eq!(r#"{"x":1,"inner":{},"empty":{}}"#, s);
+    assert_eq!(
+        Outer {
+            x: 1,
+            inner: AllSkipped { a: 0 },
+            empty: Empty {},
+        },
+        unsafe { Outer::from_str(s.as_mut_str()) }.unwrap()
+    );
+
+    let r = Rule::Match {
+        compiled: vec![1, 2, 3],
+    };
+    let mut s = r.json_string().unwrap();
+    assert_eq!(r#"{"Match":{}}"#, s);
+    assert_eq!(
+        Rule::Match {
+            compiled: Vec::new()
+        },
+        unsafe { Rule::from_str(s.as_mut_str()) }.unwrap()
+    );
+}
@@ -96,3 +96,51 @@ fn skip_in_enum() {
     let s = b.json_string().unwrap();
     assert_eq!(r#"{"Blargh":{"f1":1,"f3":3}}"#, s);
 }
+
+#[test]
+fn skip_serializing_none() {
+    #[derive(Serialize)]
+    #[simd_json(skip_serializing_none)]
+    struct Dto {
+        id: u8,
+        name: Option<String>,
+        email: std::option::Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<u8>,
+        #[serde(skip_serializing_if = "has_no_age")]
+        age: Option<u8>,
+    }
+
+    fn has_no_age(age: &Option<u8>) -> bool {
+        age.map_or(true, |a| a == 0)
+    }
+
+    let d = Dto {
+        id: 1,
+        name: None,
+        email: Some("snot@badger".into()),
+        tags: vec![],
+        age: Some(0),
+    };
+    assert_eq!(
+        r#"{"id":1,"email":"snot@badger"}"#,
+        d.json_string().unwrap()
+    );
+
+    #[derive(Serialize)]
+    #[serde(skip_serializing_none)]
+    enum Event {
+        Created { id: u8, by: Option<String> },
+    }
+
+    let e = Event::Created { id: 1, by: None };
+    assert_eq!(r#"{"Created":{"id":1}}"#, e.json_string().unwrap());
+    let e = Event::Created {
+        id: 1,
+        by: Some("snot".into()),
+    };
+    assert_eq!(
+        r#"{"Created":{"id":1,"by":"snot"}}"#,
+        e.json_string().unwrap()
+    );
+}
@@ -18,6 +18,42 @@ fn unnamed2() {
     assert_eq!(r#"[1,2]"#, b.json_string().unwrap())
 }
 
+#[test]
+fn unnamed2_deser() {
+    #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
+    struct Point(f64, f64);
+    #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
+    struct Span<'a>(u32, u32, &'a str);
+
+    let p = Point(1.5, -2.0);
+    let mut s = p.json_string().unwrap();
+    assert_eq!(r#"[1.5,-2.0]"#, s);
+    assert_eq!(p, unsafe { Point::from_str(s.as_mut_str()) }.unwrap());
+
+    let mut s = r#"[1,2,"snot"]"#.to_string();
+    assert_eq!(
+        Span(1, 2, "snot"),
+        Span::from_slice(unsafe { s.as_bytes_mut() }).unwrap()
+    );
+
+    let mut s = r#"[1,2]"#.to_string();
+    let err = Span::from_slice(unsafe { s.as_bytes_mut() }).unwrap_err();
+    assert_eq!(
+        err,
+        simd_json_derive::de::Error::InvalidLength {
+            expected: 3,
+            got: 2
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "invalid length 2, expected an array of length 3"
+    );
+
+    let mut s = r#"{"0":1,"1":2}"#.to_string();
+    assert!(unsafe { Point::from_str(s.as_mut_str()) }.is_err());
+}
+
 #[test]
 fn named() {
     #[derive(simd_json_derive::Serialize, simd_json_derive::Deserialize, PartialEq, Debug)]
@@ -109,3 +145,83 @@ fn tpl_array() {
         assert_eq!(b, Bla::from_str(s.as_mut_str()).unwrap());
     }
 }
+
+#[test]
+fn unit() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Marker;
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[simd_json(unit_as_object)]
+    struct Empty;
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Wrapper {
+        Marker(Marker),
+        Empty(Empty),
+    }
+
+    let mut s = Marker.json_string().unwrap();
+    assert_eq!("null", s);
+    assert_eq!(Marker, unsafe { Marker::from_str(s.as_mut_str()) }.unwrap());
+    let mut s = "{}".to_string();
+    assert!(unsafe { Marker::from_str(s.as_mut_str()) }.is_err());
+
+    let mut s = Empty.json_string().unwrap();
+    assert_eq!("{}", s);
+    assert_eq!(Empty, unsafe { Empty::from_str(s.as_mut_str()) }.unwrap());
+    let mut s = r#"{"snot":"badger"}"#.to_string();
+    assert_eq!(Empty, unsafe { Empty::from_str(s.as_mut_str()) }.unwrap());
+    let mut s = "null".to_string();
+    assert!(unsafe { Empty::from_str(s.as_mut_str()) }.is_err());
+
+    let w = Wrapper::Marker(Marker);
+    let mut s = w.json_string().unwrap();
+    assert_eq!(r#"{"Marker":null}"#, s);
+    assert_eq!(w, unsafe { Wrapper::from_str(s.as_mut_str()) }.unwrap());
+
+    let w = Wrapper::Empty(Empty);
+    let mut s = w.json_string().unwrap();
+    assert_eq!(r#"{"Empty":{}}"#, s);
+    assert_eq!(w, unsafe { Wrapper::from_str(s.as_mut_str()) }.unwrap());
+}
+
+#[test]
+fn transparent() {
+    use std::marker::PhantomData;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[simd_json(transparent)]
+    struct Id<T> {
+        raw: u64,
+        _t: PhantomData<T>,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(transparent)]
+    struct Cached(String, #[serde(skip)] Option<usize>);
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct User {
+        id: Id<User>,
+        name: Cached,
+    }
+
+    let u = User {
+        id: Id {
+            raw: 42,
+            _t: PhantomData,
+        },
+        name: Cached("snot".into(), Some(4)),
+    };
+    let mut s = u.json_string().unwrap();
+    assert_eq!(r#"{"id":42,"name":"snot"}"#, s);
+    assert_eq!(
+        User {
+            id: Id {
+                raw: 42,
+                _t: PhantomData,
+            },
+            name: Cached("snot".into(), None),
+        },
+        unsafe { User::from_str(s.as_mut_str()) }.unwrap()
+    );
+}
@@ -0,0 +1,206 @@
+use simd_json_derive::{Deserialize, Serialize};
+
+#[test]
+fn internally_tagged() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Push {
+        repository: String,
+        commits: u8,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[simd_json(tag = "type")]
+    enum Event {
+        Ping,
+        Push(Push),
+        Star {
+            user: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            starred: Option<bool>,
+        },
+    }
+
+    let e = Event::Ping;
+    let mut s = e.json_string().unwrap();
+    assert_eq!(r#"{"type":"Ping"}"#, s);
+    assert_eq!(e, unsafe { Event::from_str(s.as_mut_str()) }.unwrap());
+
+    let e = Event::Push(Push {
+        repository: "snot".into(),
+        commits: 3,
+    });
+    let mut s = e.json_string().unwrap();
+    assert_eq!(r#"{"type":"Push","repository":"snot","commits":3}"#, s);
+    assert_eq!(e, unsafe { Event::from_str(s.as_mut_str()) }.unwrap());
+
+    let e = Event::Star {
+        user: "badger".into(),
+        starred: None,
+    };
+    let mut s = e.json_string().unwrap();
+    assert_eq!(r#"{"type":"Star","user":"badger"}"#, s);
+    assert_eq!(e, unsafe { Event::from_str(s.as_mut_str()) }.unwrap());
+
+    let mut s = r#"{"user":"badger","starred":true,"type":"Star"}"#.to_string();
+    assert_eq!(
+        Event::Star {
+            user: "badger".into(),
+            starred: Some(true)
+        },
+        unsafe { Event::from_str(s.as_mut_str()) }.unwrap()
+    );
+
+    let mut s =
+        r#"{"repository":"snot","extra":{"type":"Ping"},"type":"Push","commits":3}"#.to_string();
+    assert_eq!(
+        Event::Push(Push {
+            repository: "snot".into(),
+            commits: 3,
+        }),
+        unsafe { Event::from_str(s.as_mut_str()) }.unwrap()
+    );
+
+    let mut s = r#"{"type":"Fork"}"#.to_string();
+    let err = unsafe { Event::from_str(s.as_mut_str()) }.unwrap_err();
+    assert_eq!(err.to_string(), "unknwon enum variant `Fork`");
+
+    let mut s = r#"{"user":"badger"}"#.to_string();
+    let err = unsafe { Event::from_str(s.as_mut_str()) }.unwrap_err();
+    assert_eq!(err.to_string(), "missing field: `type`");
+}
+
+#[test]
+fn adjacently_tagged() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[simd_json(tag = "t", content = "c")]
+    enum Message {
+        Ping,
+        Echo(String),
+        Move(u8, u8),
+        Login { user: String, token: Option<u64> },
+    }
+
+    let m = Message::Ping;
+    let mut s = m.json_string().unwrap();
+    assert_eq!(r#"{"t":"Ping"}"#, s);
+    assert_eq!(m, unsafe { Message::from_str(s.as_mut_str()) }.unwrap());
+
+    let m = Message::Echo("snot".into());
+    let mut s = m.json_string().unwrap();
+    assert_eq!(r#"{"t":"Echo","c":"snot"}"#, s);
+    assert_eq!(m, unsafe { Message::from_str(s.as_mut_str()) }.unwrap());
+
+    let m = Message::Move(1, 2);
+    let mut s = m.json_string().unwrap();
+    assert_eq!(r#"{"t":"Move","c":[1,2]}"#, s);
+    assert_eq!(m, unsafe { Message::from_str(s.as_mut_str()) }.unwrap());
+
+    let m = Message::Login {
+        user: "badger".into(),
+        token: Some(42),
+    };
+    let mut s = m.json_string().unwrap();
+    assert_eq!(r#"{"t":"Login","c":{"user":"badger","token":42}}"#, s);
+    assert_eq!(m, unsafe { Message::from_str(s.as_mut_str()) }.unwrap());
+
+    let mut s = r#"{"c":{"token":42,"user":"badger"},"t":"Login"}"#.to_string();
+    assert_eq!(m, unsafe { Message::from_str(s.as_mut_str()) }.unwrap());
+
+    let mut s = r#"{"c":[1,2],"t":"Move"}"#.to_string();
+    assert_eq!(
+        Message::Move(1, 2),
+        unsafe { Message::from_str(s.as_mut_str()) }.unwrap()
+    );
+
+    let mut s = r#"{"t":"Echo"}"#.to_string();
+    let err = unsafe { Message::from_str(s.as_mut_str()) }.unwrap_err();
+    assert_eq!(err.to_string(), "missing field: `c`");
+
+    let mut s = r#"{"c":"snot"}"#.to_string();
+    let err = unsafe { Message::from_str(s.as_mut_str()) }.unwrap_err();
+    assert_eq!(err.to_string(), "missing field: `t`");
+}
+
+#[test]
+fn untagged() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Login<'de> {
+        token: &'de str,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[simd_json(untagged)]
+    enum Response<'de> {
+        Login(Login<'de>),
+        Error { error: String, code: u16 },
+        Pair(u8, bool),
+        Code(u16),
+        Empty,
+    }
+
+    let r = Response::Login(Login { token: "snot" });
+    let mut s = r.json_string().unwrap();
+    assert_eq!(r#"{"token":"snot"}"#, s);
+    assert_eq!(
+        r,
+        Response::from_slice(unsafe { s.as_bytes_mut() }).unwrap()
+    );
+
+    let r = Response::Error {
+        error: "badger".into(),
+        code: 404,
+    };
+    let mut s = r.json_string().unwrap();
+    assert_eq!(r#"{"error":"badger","code":404}"#, s);
+    assert_eq!(
+        r,
+        Response::from_slice(unsafe { s.as_bytes_mut() }).unwrap()
+    );
+
+    let r = Response::Pair(1, true);
+    let mut s = r.json_string().unwrap();
+    assert_eq!(r#"[1,true]"#, s);
+    assert_eq!(
+        r,
+        Response::from_slice(unsafe { s.as_bytes_mut() }).unwrap()
+    );
+
+    let r = Response::Code(500);
+    let mut s = r.json_string().unwrap();
+    assert_eq!(r#"500"#, s);
+    assert_eq!(
+        r,
+        Response::from_slice(unsafe { s.as_bytes_mut() }).unwrap()
+    );
+
+    let r = Response::Empty;
+    let mut s = r.json_string().unwrap();
+    assert_eq!(r#"null"#, s);
+    assert_eq!(
+        r,
+        Response::from_slice(unsafe { s.as_bytes_mut() }).unwrap()
+    );
+
+    let mut s = r#""snot""#.to_string();
+    let err = Response::from_slice(unsafe { s.as_bytes_mut() }).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Custom error: data did not match any variant of untagged enum Response"
+    );
+
+    // the untagged enum only consumes its own value
+    let mut s = r#"[{"error":"badger","code":1},[2,false],"snot"]"#.to_string();
+    let v: (Response, Response, String) =
+        Deserialize::from_slice(unsafe { s.as_bytes_mut() }).unwrap();
+    assert_eq!(
+        (
+            Response::Error {
+                error: "badger".into(),
+                code: 1
+            },
+            Response::Pair(2, false),
+            "snot".to_string()
+        ),
+        v
+    );
+}
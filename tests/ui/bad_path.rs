@@ -0,0 +1,9 @@
+use simd_json_derive::Serialize;
+
+#[derive(Serialize)]
+struct Bla {
+    #[serde(skip_serializing_if = "not a path")]
+    field: Option<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use simd_json_derive::Deserialize;
+
+#[derive(Deserialize)]
+struct Bla<'a> {
+    #[serde(borrow)]
+    snot: String,
+    #[serde(borrow = "'b")]
+    badger: &'a str,
+}
+
+fn main() {}
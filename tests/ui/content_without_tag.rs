@@ -0,0 +1,9 @@
+use simd_json_derive::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(content = "c")]
+enum Bla {
+    Snot(u8),
+}
+
+fn main() {}
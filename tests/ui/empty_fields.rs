@@ -0,0 +1,13 @@
+use simd_json_derive::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Empty();
+
+#[derive(Serialize, Deserialize)]
+enum Bla {
+    Snot(),
+    Badger {},
+    Fine,
+}
+
+fn main() {}
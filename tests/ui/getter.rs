@@ -0,0 +1,9 @@
+use simd_json_derive::Serialize;
+
+#[derive(Serialize)]
+struct Bla {
+    #[serde(getter = "Bla::snot")]
+    snot: u8,
+}
+
+fn main() {}
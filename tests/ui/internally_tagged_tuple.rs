@@ -0,0 +1,9 @@
+use simd_json_derive::Serialize;
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+enum Bla {
+    Snot(u8, u8),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use simd_json_derive::Serialize;
+
+mod hex {
+    pub fn json_write<W: std::io::Write>(v: &u32, writer: &mut W) -> std::io::Result<()> {
+        write!(writer, "\"{v:x}\"")
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+enum Bla {
+    Snot(#[serde(serialize_with = "hex::json_write")] u32),
+}
+
+fn main() {}
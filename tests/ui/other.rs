@@ -0,0 +1,17 @@
+use simd_json_derive::Deserialize;
+
+#[derive(Deserialize)]
+enum Bla {
+    #[serde(other)]
+    Snot(u8, u8),
+}
+
+#[derive(Deserialize)]
+enum Blubb {
+    #[serde(other)]
+    Snot,
+    #[serde(other)]
+    Badger,
+}
+
+fn main() {}
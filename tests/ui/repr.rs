@@ -0,0 +1,24 @@
+use simd_json_derive::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+#[serde(repr = "u8")]
+struct Bla {
+    snot: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(repr = "u8")]
+enum Blubb {
+    Snot,
+    Badger(u8),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(repr = "u8")]
+enum Level {
+    Low = 1,
+    High = 300,
+    Negative = -1,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use simd_json_derive::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Pair(u32, #[simd_json(skip)] u8);
+
+#[derive(Serialize, Deserialize)]
+enum Bla {
+    Snot(#[serde(skip_serializing)] u8, u8),
+}
+
+fn main() {}
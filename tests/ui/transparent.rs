@@ -0,0 +1,16 @@
+use simd_json_derive::Serialize;
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Pair {
+    left: u8,
+    right: u8,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+enum Bla {
+    Snot(u8),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use simd_json_derive::Deserialize;
+
+#[derive(Deserialize)]
+union Bla {
+    snot: u8,
+    badger: u16,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use simd_json_derive::Deserialize;
+
+#[derive(Deserialize)]
+#[simd_json(deny_unknown_field)]
+struct Bla {
+    field: u8,
+}
+
+fn main() {}
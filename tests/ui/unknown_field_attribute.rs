@@ -0,0 +1,9 @@
+use simd_json_derive::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Bla {
+    #[simd_json(renam = "f")]
+    field: u8,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use simd_json_derive::Serialize;
+
+#[derive(Serialize)]
+#[serde(rename_all = "Train-Case")]
+struct Bla {
+    field_one: u8,
+}
+
+fn main() {}
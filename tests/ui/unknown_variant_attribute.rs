@@ -0,0 +1,10 @@
+use simd_json_derive::Serialize;
+
+#[derive(Serialize)]
+enum Bla {
+    #[serde(skip)]
+    Snot,
+    Badger,
+}
+
+fn main() {}
@@ -0,0 +1,131 @@
+use simd_json_derive::{de, Deserialize, Serialize, Tape};
+use std::io::Write;
+
+mod hex {
+    use super::*;
+
+    pub fn json_write<W: Write>(v: &u32, writer: &mut W) -> std::io::Result<()> {
+        write!(writer, "\"{v:x}\"")
+    }
+
+    pub fn from_tape(tape: &mut Tape) -> de::Result<u32> {
+        let s: &str = Deserialize::from_tape(tape)?;
+        u32::from_str_radix(s, 16).map_err(|e| de::Error::custom(e.to_string()))
+    }
+}
+
+fn write_upper<W: Write>(v: &str, writer: &mut W) -> std::io::Result<()> {
+    v.to_uppercase().json_write(writer)
+}
+
+fn read_lower<'input>(tape: &mut Tape<'input>) -> de::Result<String> {
+    String::from_tape(tape).map(|s| s.to_lowercase())
+}
+
+#[test]
+fn with_in_struct() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Record {
+        #[simd_json(with = "hex")]
+        id: u32,
+        #[serde(serialize_with = "write_upper")]
+        name: String,
+        #[serde(deserialize_with = "read_lower")]
+        tag: String,
+    }
+
+    let r = Record {
+        id: 255,
+        name: "snot".into(),
+        tag: "Badger".into(),
+    };
+    let mut s = r.json_string().unwrap();
+    assert_eq!(r#"{"id":"ff","name":"SNOT","tag":"Badger"}"#, s);
+    assert_eq!(
+        Record {
+            id: 255,
+            name: "SNOT".into(),
+            tag: "badger".into(),
+        },
+        unsafe { Record::from_str(s.as_mut_str()) }.unwrap()
+    );
+}
+
+#[test]
+fn with_in_enum() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Event {
+        Created {
+            #[serde(with = "hex")]
+            id: u32,
+        },
+    }
+
+    let e = Event::Created { id: 4096 };
+    let mut s = e.json_string().unwrap();
+    assert_eq!(r#"{"Created":{"id":"1000"}}"#, s);
+    assert_eq!(e, unsafe { Event::from_str(s.as_mut_str()) }.unwrap());
+}
+
+#[test]
+fn with_in_tuples() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Id(#[simd_json(with = "hex")] u32);
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Pair(#[serde(with = "hex")] u32, u8);
+
+    mod wrapped {
+        use super::*;
+
+        pub fn json_write<T, W: Write>(_: &T, writer: &mut W) -> std::io::Result<()> {
+            writer.write_all(b"null")
+        }
+
+        pub fn from_tape<T: Default>(tape: &mut Tape) -> de::Result<T> {
+            <()>::from_tape(tape).map(|_| T::default())
+        }
+    }
+
+    // `T` needs no `Serialize` or `Deserialize` bound as it is only written with `wrapped`
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Wrapper<T: Default>(#[simd_json(with = "wrapped")] T);
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Event {
+        Deleted(#[simd_json(with = "hex")] u32),
+        Moved(
+            #[simd_json(with = "hex")] u32,
+            #[simd_json(with = "hex")] u32,
+        ),
+    }
+
+    let mut s = Id(255).json_string().unwrap();
+    assert_eq!(r#""ff""#, s);
+    assert_eq!(Id(255), unsafe { Id::from_str(s.as_mut_str()) }.unwrap());
+
+    let mut s = Pair(255, 7).json_string().unwrap();
+    assert_eq!(r#"["ff",7]"#, s);
+    assert_eq!(
+        Pair(255, 7),
+        unsafe { Pair::from_str(s.as_mut_str()) }.unwrap()
+    );
+
+    #[derive(Default, PartialEq, Debug)]
+    struct NoJson;
+
+    let mut s = Wrapper(NoJson).json_string().unwrap();
+    assert_eq!("null", s);
+    assert_eq!(
+        Wrapper(NoJson),
+        unsafe { Wrapper::<NoJson>::from_str(s.as_mut_str()) }.unwrap()
+    );
+
+    let events = vec![Event::Deleted(255), Event::Moved(16, 17)];
+    let mut s = events.json_string().unwrap();
+    assert_eq!(r#"[{"Deleted":"ff"},{"Moved":["10","11"]}]"#, s);
+    assert_eq!(
+        events,
+        unsafe { Vec::<Event>::from_str(s.as_mut_str()) }.unwrap()
+    );
+}